@@ -0,0 +1,135 @@
+use ndarray::Array2;
+use cgp_core::value_domain::ValueDomain;
+use crate::utils::boolean_functions as bf;
+use crate::utils::boolean_functions::BooleanGate;
+use crate::utils::fitness_metrics;
+
+/// Boolean circuits built from a selection of the gate library. Function ids are indices
+/// into `gates`.
+/// Values are packed truth-table columns of 64 rows per word, see `to_packed_columns`;
+/// `nbr_samples` is the number of rows of the truth table.
+#[derive(Clone)]
+pub struct BooleanDomain {
+    pub gates: Vec<BooleanGate>,
+    pub nbr_samples: usize,
+}
+
+impl BooleanDomain {
+    pub fn new(gates: Vec<BooleanGate>, nbr_samples: usize) -> Self {
+        Self {
+            gates,
+            nbr_samples,
+        }
+    }
+}
+
+impl ValueDomain for BooleanDomain {
+    type Value = u64;
+    type OutputTransform = ();
+
+    fn nbr_functions(&self) -> usize {
+        self.gates.len()
+    }
+
+    fn arity(&self, function_id: usize) -> usize {
+        self.gates[function_id].arity
+    }
+
+    fn execute(&self, function_id: usize, inputs: &[&[u64]], output: &mut [u64]) {
+        (self.gates[function_id].execute)(inputs, output)
+    }
+
+    fn display_function(&self, function_id: usize, inputs: &[String]) -> String {
+        bf::display_gate(&self.gates[function_id], inputs)
+    }
+
+    fn fitness(&self, outputs: &[&[u64]], labels: &[Vec<u64>]) -> f32 {
+        fitness_metrics::fitness_boolean(outputs, labels, self.nbr_samples)
+    }
+}
+
+/// Splits a truth table of shape (rows, columns) into one packed vector per column. Bit `i`
+/// of word `w` holds row `64 * w + i`; the padding of the last word is 0.
+pub fn to_packed_columns(table: &Array2<bool>) -> Vec<Vec<u64>> {
+    table.columns()
+        .into_iter()
+        .map(|column| {
+            let mut words = vec![0u64; column.len().div_ceil(64)];
+            for (row, bit) in column.iter().enumerate() {
+                words[row / 64] |= (*bit as u64) << (row % 64);
+            }
+            words
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use crate::utils::boolean_functions::GATE_LIBRARY;
+    use super::*;
+
+    /// Not a multiple of 64, so the last word is padded.
+    const NBR_ROWS: usize = 100;
+
+    /// A truth table of `NBR_ROWS` rows with irregular bit patterns.
+    fn get_table(nbr_columns: usize, seed: usize) -> Array2<bool> {
+        Array2::from_shape_fn((NBR_ROWS, nbr_columns), |(row, column)| (row * 7 + column * 3 + seed) % 5 < 2)
+    }
+
+    fn evaluate_row(gate: &BooleanGate, x: &[bool]) -> bool {
+        match gate.name {
+            "and" => x[0] & x[1],
+            "or" => x[0] | x[1],
+            "nand" => !(x[0] & x[1]),
+            "nor" => !(x[0] | x[1]),
+            "xor" => x[0] ^ x[1],
+            "xnor" => !(x[0] ^ x[1]),
+            "not" => !x[0],
+            "buffer" => x[0],
+            "mux" => if x[0] { x[1] } else { x[2] },
+            name => panic!("no row-wise evaluation for {}", name),
+        }
+    }
+
+    #[test]
+    fn to_packed_columns_keeps_rows_and_clears_padding() {
+        let table = get_table(3, 0);
+        let packed = to_packed_columns(&table);
+
+        assert_eq!(packed.len(), 3);
+        for (column, words) in packed.iter().enumerate() {
+            assert_eq!(words.len(), 2);
+            for row in 0..128 {
+                let bit = (words[row / 64] >> (row % 64)) & 1 == 1;
+                let expected = row < NBR_ROWS && table[[row, column]];
+                assert_eq!(bit, expected, "row {} of column {}", row, column);
+            }
+        }
+    }
+
+    #[test]
+    fn packed_fitness_matches_row_wise_evaluation() {
+        let inputs = get_table(3, 0);
+        let label = get_table(1, 1);
+        let packed_inputs = to_packed_columns(&inputs);
+        let packed_label = to_packed_columns(&label);
+        let domain = BooleanDomain::new(GATE_LIBRARY.to_vec(), NBR_ROWS);
+
+        for (function_id, gate) in GATE_LIBRARY.iter().enumerate() {
+            let gate_inputs: Vec<&[u64]> = packed_inputs[..gate.arity].iter().map(|column| column.as_slice()).collect();
+            let mut output = vec![0u64; packed_label[0].len()];
+            domain.execute(function_id, &gate_inputs, &mut output);
+
+            let nbr_wrong = inputs.rows()
+                .into_iter()
+                .zip(label.column(0).iter())
+                .filter(|(row, expected)| evaluate_row(gate, row.as_slice().unwrap()) != **expected)
+                .count();
+
+            // inverting gates set the padding bits of the last word, which must not be scored
+            assert_float_eq!(domain.fitness(&[&output], &packed_label), nbr_wrong as f32 / NBR_ROWS as f32, abs <= 1e-6,
+                             "gate {}", gate.name);
+        }
+    }
+}
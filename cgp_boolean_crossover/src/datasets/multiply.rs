@@ -135,6 +135,6 @@ pub fn get_dataset() -> (Array2<bool>, Array2<bool>) {
 
     let data = arr2(&data);
     let labels = arr2(&labels);
-    return (data, labels);
+    (data, labels)
 }
 
@@ -1,8 +1,3 @@
-pub mod utils;
-pub mod global_params;
-pub mod datasets;
-pub mod standard_cgp;
-pub mod reorder;
-
-
-
+pub mod utils;
+pub mod datasets;
+pub mod boolean_domain;
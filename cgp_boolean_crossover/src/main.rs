@@ -1,18 +1,22 @@
 use float_eq::float_eq;
 use std::io::Write;
-use cgp_boolean_crossover::global_params::CgpParameters;
+use cgp_core::global_params::CgpParameters;
+use cgp_core::genotype::Genotype;
+use cgp_core::standard_cgp::chromosome::Chromosome as StandardChromosome;
+use cgp_core::reorder::chromosome_reorder_equidistant::Chromosome as ReorderChromosome;
+use cgp_core::dag::chromosome_dag::Chromosome as DagChromosome;
 use cgp_boolean_crossover::datasets::*;
+use cgp_boolean_crossover::boolean_domain::{BooleanDomain, to_packed_columns};
+use cgp_boolean_crossover::utils::boolean_functions::{get_gate_set, DEFAULT_GATES};
 use clap::Parser;
 use std::fs;
 use std::fs::File;
 use std::path::Path;
 
-#[cfg(feature = "mulambda")]
-use cgp_boolean_crossover::utils::runner::Runner;
-#[cfg(feature = "tournament")]
-use cgp_boolean_crossover::utils::runner_multiple_parents_with_elitist_tournament::Runner;
-#[cfg(feature = "mulambda_crossover")]
-use cgp_boolean_crossover::utils::runner_multiple_parents_with_elitist_mulambda::Runner;
+use cgp_core::utils::evolutionary_runner::EvolutionaryRunner;
+use cgp_core::utils::runner::Runner as OnePlusFourRunner;
+use cgp_core::utils::runner_multiple_parents_with_elitist_mulambda::Runner as MuLambdaRunner;
+use cgp_core::utils::runner_multiple_parents_with_elitist_tournament::Runner as TournamentRunner;
 
 #[derive(Parser)]
 #[clap(author, version, about, name = "testname")]
@@ -26,8 +30,17 @@ struct Args {
     #[arg(long, default_value_t = 500)]
     nbr_nodes: usize,
 
-    //         0 => format!("Baseline_standard"),
-    //         1 => format!("ereorder"),
+    // the computational nodes form a grid with nbr_rows rows; nbr_nodes must be divisible by it
+    #[arg(long, default_value_t = 1)]
+    nbr_rows: usize,
+
+    // number of preceding columns a node may connect to; 0: unrestricted
+    #[arg(long, default_value_t = 0)]
+    levels_back: usize,
+
+    // 0: standard CGP
+    // 1: CGP with E-Reorder
+    // 2: DAG-CGP; connections may point to any node as long as the graph stays acyclic
     #[arg(long, default_value_t = 1)]
     cgp_type: usize,
 
@@ -35,6 +48,11 @@ struct Args {
     // 1: multi-n crossover
     // 2: uniform crossover
     // 3: no crossover
+    // 4: subgraph crossover
+    // 5: discrete crossover; swaps single genes
+    // 6: block crossover; swaps a block of active nodes
+    // 7: output swap crossover; swaps the cones of single output nodes
+    // 8: homologous crossover; aligns parents by the rank of their active nodes
     #[arg(long, default_value_t = 2)]
     crossover_type: usize,
 
@@ -42,6 +60,10 @@ struct Args {
     #[arg(long, default_value_t = 0.9)]
     crossover_rate: f32,
 
+    // point, multi-n and uniform crossover exchange output nodes as well
+    #[arg(long)]
+    crossover_include_outputs: bool,
+
     #[arg(long, default_value_t = 8)]
     tournament_size: usize,
 
@@ -55,11 +77,31 @@ struct Args {
     #[arg(long, default_value_t = 3)]
     multi_point_n: usize,
 
-    //         0 => format!("one_plus_four"),
-    //         1 => format!("mu_{}_lambda_{}", args.elitism_number, args.population_size),
-    //         2 => format!("tournament_pop_size_{}_t_size_{}_elitism_{}", args.population_size, args.tournament_size, args.elitism_number),
+    // 0: (1+4)-ES
+    // 1: (mu+lambda)-ES with crossover; mu = elitism_number, lambda = population_size
+    // 2: tournament selection with elitists
     #[arg(long, default_value_t = 0)]
     runner_type: usize,
+
+    // 0: single active mutation
+    // 1: probabilistic point mutation; every gene is mutated with mutation_rate
+    // 2: mutates until mutation_k genes of active nodes were changed
+    #[arg(long, default_value_t = 0)]
+    mutation_type: usize,
+
+    #[arg(long, default_value_t = 0.05)]
+    mutation_rate: f32,
+
+    #[arg(long, default_value_t = 1)]
+    mutation_k: usize,
+
+    // comma separated selection of the gate library:
+    // and, or, nand, nor, xor, xnor, not, buffer, mux
+    #[arg(long, value_delimiter = ',', default_values_t = DEFAULT_GATES.map(String::from))]
+    gates: Vec<String>,
+
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
 
@@ -82,20 +124,32 @@ fn main() {
     params.nbr_inputs = nbr_inputs;
     params.nbr_outputs = nbr_outputs;
     params.nbr_computational_nodes = args.nbr_nodes;
+    assert_eq!(args.nbr_nodes % args.nbr_rows, 0, "nbr_nodes must be divisible by nbr_rows");
+    params.nbr_rows = args.nbr_rows;
+    params.nbr_columns = args.nbr_nodes / args.nbr_rows;
+    params.levels_back = match args.levels_back {
+        0 => params.nbr_columns,
+        levels_back => levels_back,
+    };
     params.crossover_type = args.crossover_type;
     params.crossover_rate = args.crossover_rate;
+    params.crossover_include_outputs = args.crossover_include_outputs;
     params.tournament_size = args.tournament_size;
     params.elitism_number = args.elitism_number;
     params.multi_point_n = args.multi_point_n;
     params.population_size = args.population_size;
     params.cgp_type = args.cgp_type;
+    params.mutation_type = args.mutation_type;
+    params.mutation_rate = args.mutation_rate;
+    params.mutation_k = args.mutation_k;
+    params.seed = args.seed;
 
 
     // ################################################################################
     // ############################ Logger ############################################
     // ################################################################################
     let runner_type = match args.runner_type {
-        0 => format!("one_plus_four"),
+        0 => "one_plus_four".to_string(),
         1 => format!("mu_{}_lambda_{}", args.elitism_number, args.population_size),
         2 => format!("tournament_pop_size_{}_t_size_{}_elitism_{}", args.population_size, args.tournament_size, args.elitism_number),
         _ => panic!("wrong runner type"),
@@ -110,18 +164,51 @@ fn main() {
     };
 
     let cgp_type_string = match args.cgp_type {
-        0 => format!("Baseline_standard"),
-        1 => format!("ereorder"),
+        0 => "Baseline_standard",
+        1 => "ereorder",
+        2 => "dag",
         _ => panic!("Wrong type"),
     };
 
     let crossover_type = match args.crossover_type {
-        0 => format!("point_crossover"),
+        0 => "point_crossover".to_string(),
         1 => format!("multi_{}_crossover", args.multi_point_n),
-        2 => format!("uniform_crossover"),
-        3 => format!("no_crossover"),
+        2 => "uniform_crossover".to_string(),
+        3 => "no_crossover".to_string(),
+        4 => "subgraph_crossover".to_string(),
+        5 => "discrete_crossover".to_string(),
+        6 => "block_crossover".to_string(),
+        7 => "output_swap_crossover".to_string(),
+        8 => "homologous_crossover".to_string(),
         _ => panic!("Wrong type"),
     };
+    let crossover_type = if args.crossover_include_outputs {
+        format!("{}_with_outputs", crossover_type)
+    } else {
+        crossover_type
+    };
+
+    // only a non-default gate set changes the folder name
+    let gates_string = if args.gates == DEFAULT_GATES {
+        String::new()
+    } else {
+        format!("_gates_{}", args.gates.join("_"))
+    };
+
+    // only a non-default mutation changes the folder name
+    let mutation_string = match args.mutation_type {
+        0 => String::new(),
+        1 => format!("_mutation_prob_{}", args.mutation_rate),
+        2 => format!("_mutation_k_active_{}", args.mutation_k),
+        _ => panic!("Wrong mutation type"),
+    };
+
+    // only a restricted grid changes the folder name
+    let grid_string = if params.nbr_rows == 1 && params.levels_back == params.nbr_columns {
+        String::new()
+    } else {
+        format!("_rows_{}_levels_back_{}", params.nbr_rows, params.levels_back)
+    };
 
     let save_path = Path::new("")
         .join("Experiments_Output_boolean")
@@ -129,29 +216,47 @@ fn main() {
         .join(cgp_type_string)
         .join(crossover_type)
         .join(dataset_string)
-        .join(format!("number_nodes_{}_{}", args.nbr_nodes, "single"));
+        .join(format!("number_nodes_{}_{}{}{}{}", args.nbr_nodes, "single", grid_string, gates_string, mutation_string));
 
-    fs::create_dir_all(save_path.clone()).unwrap();
+    fs::create_dir_all(&save_path).unwrap();
+
+    // 64 rows of the truth table per word, so each gate evaluates 64 rows at once
+    let nbr_samples = data.nrows();
+    let data = to_packed_columns(&data);
+    let label = to_packed_columns(&label);
+    let domain = BooleanDomain::new(get_gate_set(&args.gates), nbr_samples);
+
+    match args.cgp_type {
+        0 => run::<StandardChromosome<BooleanDomain>>(&args, params, domain, data, label, &save_path),
+        1 => run::<ReorderChromosome<BooleanDomain>>(&args, params, domain, data, label, &save_path),
+        2 => run::<DagChromosome<BooleanDomain>>(&args, params, domain, data, label, &save_path),
+        _ => panic!("Wrong type"),
+    }
+}
 
+fn run<G: Genotype<BooleanDomain>>(args: &Args,
+                                   params: CgpParameters,
+                                   domain: BooleanDomain,
+                                   data: Vec<Vec<u64>>,
+                                   label: Vec<Vec<u64>>,
+                                   save_path: &Path) {
     // ################################################################################
     // ############################ Training ##########################################
     // ################################################################################
 
-    let stdout = std::io::stdout();
-    let mut lock = stdout.lock();
-
-
     let save_file_iteration = format!("run_{}_iteration.txt", args.run_id);
     let mut output_file = File::create(save_path.join(save_file_iteration))
         .expect("cannot create file");
 
     let mut runtime = 0;
-    let mut runner = Runner::new(params.clone(), data, label);
+    let mut runner: Box<dyn EvolutionaryRunner<BooleanDomain, G>> = match args.runner_type {
+        0 => Box::new(OnePlusFourRunner::new(params, domain, data.clone(), label.clone(), data, label)),
+        1 => Box::new(MuLambdaRunner::new(params, domain, data.clone(), label.clone(), data, label)),
+        2 => Box::new(TournamentRunner::new(params, domain, data.clone(), label.clone(), data, label)),
+        _ => panic!("wrong runner type"),
+    };
 
     while runtime < 500_000 {
-        // if runtime % params.eval_after_iterations == 0 {
-        //     writeln!(lock, "Iteration: {runtime}, Fitness: {:?}", runner.get_best_fitness()).expect("write not okay??");
-        // }
         writeln!(output_file, "Iteration: {runtime}, Fitness: {:?}", runner.get_best_fitness()).expect("write not okay??");
 
         runner.learn_step(runtime);
@@ -168,7 +273,11 @@ fn main() {
     // ############################ Saving to text ####################################
     // ################################################################################
     println!("{runtime}");
-    write!(output_file, "End at iteration: {}", runtime).expect("cannot write");
+    writeln!(output_file, "End at iteration: {}", runtime).expect("cannot write");
+    writeln!(output_file, "Evaluations: {}", runner.get_nbr_evaluations()).expect("cannot write");
+    writeln!(output_file, "Cache hits: {}", runner.get_nbr_cache_hits()).expect("cannot write");
+    writeln!(output_file, "Cache misses: {}", runner.get_nbr_cache_misses()).expect("cannot write");
+    writeln!(output_file, "Reorder skips: {}", runner.get_nbr_reorder_skips()).expect("cannot write");
 
     let save_file_active_node = format!("run_{}_active_node.txt", args.run_id);
     let mut output = File::create(save_path.join(save_file_active_node))
@@ -176,6 +285,12 @@ fn main() {
     let mut parent = runner.get_best_solution();
     parent.get_active_nodes_id();
 
-    write!(output, "{:?}", parent.active_nodes.unwrap()).expect("cannot write");
+    write!(output, "{:?}", parent.active_nodes()).expect("cannot write");
+
+    let save_file_program = format!("run_{}_program.txt", args.run_id);
+    let mut output = File::create(save_path.join(save_file_program))
+        .expect("cannot create file");
+
+    write!(output, "{}", parent.active_program()).expect("cannot write");
 }
 
@@ -1,17 +1,99 @@
-use ndarray::prelude::*;
-
-pub fn and(con1: &ArrayView1<bool>, con2: &ArrayView1<bool>) -> Array1<bool> {
-    return con1 & con2;
-}
-
-pub fn or(con1: &ArrayView1<bool>, con2: &ArrayView1<bool>) -> Array1<bool> {
-    return con1 | con2;
-}
-
-pub fn nand(con1: &ArrayView1<bool>, con2: &ArrayView1<bool>) -> Array1<bool> {
-    return !and(con1, con2);
-}
-
-pub fn nor(con1: &ArrayView1<bool>, con2: &ArrayView1<bool>) -> Array1<bool> {
-    return !or(con1, con2);
-}
\ No newline at end of file
+// Gates work on packed truth-table columns: bit `i` of word `w` holds row `64 * w + i`, so
+// each operation evaluates 64 rows at once. Padding bits of the last word are unspecified.
+// The result is written into a preallocated output column of the same length as the inputs.
+
+pub fn and(con1: &[u64], con2: &[u64], output: &mut [u64]) {
+    binary(con1, con2, output, |a, b| a & b)
+}
+
+pub fn or(con1: &[u64], con2: &[u64], output: &mut [u64]) {
+    binary(con1, con2, output, |a, b| a | b)
+}
+
+pub fn nand(con1: &[u64], con2: &[u64], output: &mut [u64]) {
+    binary(con1, con2, output, |a, b| !(a & b))
+}
+
+pub fn nor(con1: &[u64], con2: &[u64], output: &mut [u64]) {
+    binary(con1, con2, output, |a, b| !(a | b))
+}
+
+pub fn xor(con1: &[u64], con2: &[u64], output: &mut [u64]) {
+    binary(con1, con2, output, |a, b| a ^ b)
+}
+
+pub fn xnor(con1: &[u64], con2: &[u64], output: &mut [u64]) {
+    binary(con1, con2, output, |a, b| !(a ^ b))
+}
+
+pub fn not(con1: &[u64], output: &mut [u64]) {
+    for (o, &a) in output.iter_mut().zip(con1.iter()) {
+        *o = !a;
+    }
+}
+
+pub fn buffer(con1: &[u64], output: &mut [u64]) {
+    output.copy_from_slice(con1);
+}
+
+/// Selects `con2` where `con1` is set, `con3` otherwise.
+pub fn mux(con1: &[u64], con2: &[u64], con3: &[u64], output: &mut [u64]) {
+    for (((o, &s), &a), &b) in output.iter_mut().zip(con1.iter()).zip(con2.iter()).zip(con3.iter()) {
+        *o = (s & a) | (!s & b);
+    }
+}
+
+#[inline(always)]
+fn binary(con1: &[u64], con2: &[u64], output: &mut [u64], f: impl Fn(u64, u64) -> u64) {
+    for ((o, &a), &b) in output.iter_mut().zip(con1.iter()).zip(con2.iter()) {
+        *o = f(a, b);
+    }
+}
+
+/// An entry of the gate library.
+#[derive(Clone, Copy)]
+pub struct BooleanGate {
+    pub name: &'static str,
+    pub arity: usize,
+    /// `inputs` holds `arity` packed columns, the result is written into the last argument.
+    pub execute: fn(&[&[u64]], &mut [u64]),
+    /// Display form; `{0}`, `{1}`, ... are replaced by the inputs.
+    pub display: &'static str,
+}
+
+pub const GATE_LIBRARY: [BooleanGate; 9] = [
+    BooleanGate { name: "and", arity: 2, execute: |x, out| and(x[0], x[1], out), display: "({0} & {1})" },
+    BooleanGate { name: "or", arity: 2, execute: |x, out| or(x[0], x[1], out), display: "({0} | {1})" },
+    BooleanGate { name: "nand", arity: 2, execute: |x, out| nand(x[0], x[1], out), display: "!({0} & {1})" },
+    BooleanGate { name: "nor", arity: 2, execute: |x, out| nor(x[0], x[1], out), display: "!({0} | {1})" },
+    BooleanGate { name: "xor", arity: 2, execute: |x, out| xor(x[0], x[1], out), display: "({0} ^ {1})" },
+    BooleanGate { name: "xnor", arity: 2, execute: |x, out| xnor(x[0], x[1], out), display: "!({0} ^ {1})" },
+    BooleanGate { name: "not", arity: 1, execute: |x, out| not(x[0], out), display: "!{0}" },
+    BooleanGate { name: "buffer", arity: 1, execute: |x, out| buffer(x[0], out), display: "{0}" },
+    BooleanGate { name: "mux", arity: 3, execute: |x, out| mux(x[0], x[1], x[2], out), display: "({0} ? {1} : {2})" },
+];
+
+/// The gate set used before the library existed; keeps the function ids of old runs.
+pub const DEFAULT_GATES: [&str; 4] = ["and", "or", "nand", "nor"];
+
+/// Looks up the library entries for `names`, in the given order.
+pub fn get_gate_set(names: &[String]) -> Vec<BooleanGate> {
+    assert!(!names.is_empty(), "The gate set must not be empty");
+
+    names.iter()
+        .map(|name| {
+            *GATE_LIBRARY.iter()
+                .find(|gate| gate.name == name.trim())
+                .unwrap_or_else(|| panic!("Unknown gate: {}", name))
+        })
+        .collect()
+}
+
+/// Fills the display form of `gate` with `inputs`.
+pub fn display_gate(gate: &BooleanGate, inputs: &[String]) -> String {
+    inputs.iter()
+        .enumerate()
+        .fold(gate.display.to_string(), |display, (i, input)| {
+            display.replace(&format!("{{{}}}", i), input)
+        })
+}
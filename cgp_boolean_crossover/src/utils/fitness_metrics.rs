@@ -1,14 +1,42 @@
-use ndarray::prelude::*;
-// use ndarray::Zip;
-
-
-pub fn fitness_boolean(output: &ArrayView2<bool>, labels: &Array2<bool>) -> f32 {
-    let mut fitness: i32 = 0;
-    // Zip::from(output).and(labels).for_each(|x, y| { if x == y { fitness += 1 } });
-    let nbr_correct = output ^ labels;
-    let nbr_correct = !nbr_correct;
-    nbr_correct.map(|x| if *x { fitness += 1 });
-
-    let fitness = 1. - (fitness as f32 / labels.len() as f32);
-    return fitness;
-}
\ No newline at end of file
+/// Share of wrongly predicted bits over all packed output columns. Only the first
+/// `nbr_samples` bits of each column are scored, the padding of the last word is ignored.
+pub fn fitness_boolean(outputs: &[&[u64]], labels: &[Vec<u64>], nbr_samples: usize) -> f32 {
+    assert_eq!(outputs.len(), labels.len());
+    let mut nbr_wrong: usize = 0;
+    let mut nbr_bits: usize = 0;
+
+    for (output, label) in outputs.iter().zip(labels.iter()) {
+        assert_eq!(output.len(), label.len());
+        nbr_wrong += output.iter()
+            .zip(label.iter())
+            .enumerate()
+            .map(|(word_id, (x, y))| ((x ^ y) & word_mask(word_id, nbr_samples)).count_ones() as usize)
+            .sum::<usize>();
+        nbr_bits += nbr_samples;
+    }
+
+    let nbr_correct = nbr_bits - nbr_wrong;
+    1. - (nbr_correct as f32 / nbr_bits as f32)
+}
+
+/// Bits of word `word_id` that hold one of the first `nbr_samples` rows.
+fn word_mask(word_id: usize, nbr_samples: usize) -> u64 {
+    let nbr_rows = nbr_samples.saturating_sub(word_id * 64);
+    if nbr_rows >= 64 { u64::MAX } else { (1 << nbr_rows) - 1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padding_bits_are_not_scored() {
+        // 70 rows: a full word and 6 rows of the second one
+        let label = vec![vec![u64::MAX, (1 << 6) - 1]];
+
+        assert_eq!(fitness_boolean(&[&[u64::MAX, u64::MAX]], &label, 70), 0.);
+        assert_eq!(fitness_boolean(&[&[0, !((1 << 6) - 1)]], &label, 70), 1.);
+        // 3 wrong rows in the second word, out of 70
+        assert_eq!(fitness_boolean(&[&[u64::MAX, (1 << 3) - 1]], &label, 70), 1. - 67. / 70.);
+    }
+}
@@ -1,12 +1,2 @@
-pub mod boolean_functions;
-pub mod fitness_metrics;
-pub mod node_type;
-// pub mod cycle_checker;
-pub mod runner;
-pub mod crossover;
-pub mod utility_funcs;
-
-#[cfg(feature = "tournament")]
-pub mod runner_multiple_parents_with_elitist_tournament;
-#[cfg(feature = "mulambda_crossover")]
-pub mod runner_multiple_parents_with_elitist_mulambda;
+pub mod boolean_functions;
+pub mod fitness_metrics;
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use nohash_hasher::BuildNoHashHasher;
+use petgraph::algo::toposort;
+use petgraph::graph::NodeIndex;
+use petgraph::prelude::StableGraph;
+use rand_chacha::ChaCha8Rng;
+use crate::genotype::{get_active_nodes, new_nodes_grid, output_node_ids, CgpNode, Genotype, Phenotype};
+use crate::global_params::CgpParameters as g_params;
+use crate::dag::node_dag::NodeDAG;
+use crate::utils::node_type::NodeType;
+use crate::utils::cycle_checker::CGPEdges;
+use crate::value_domain::ValueDomain;
+
+
+#[derive(Clone)]
+pub struct Chromosome<D: ValueDomain> {
+    pub params: g_params,
+    pub domain: D,
+    pub nodes_grid: Vec<NodeDAG>,
+    pub output_node_ids: Vec<usize>,
+    pub active_nodes: Option<Vec<usize>>,
+    /// Fitted to the training data by the last call of `evaluate`.
+    pub output_transform: D::OutputTransform,
+    pub phenotype: Phenotype<D::Value>,
+    pub cgp_edges: CGPEdges,
+}
+
+impl<D: ValueDomain> Display for Chromosome<D> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "+++++++++++++++++ Chromosome +++++++++++")?;
+        writeln!(f, "Nodes:")?;
+        for node in &self.nodes_grid {
+            write!(f, "{}", *node)?;
+        }
+        writeln!(f, "Active_nodes: {:?}", self.active_nodes)?;
+        writeln!(f, "Output_nodes: {:?}", self.output_node_ids)
+    }
+}
+
+impl<D: ValueDomain> Genotype<D> for Chromosome<D> {
+    type Node = NodeDAG;
+
+    fn new(params: g_params, domain: D, rng: &mut ChaCha8Rng) -> Self {
+        let nbr_functions = domain.nbr_functions();
+        let max_arity = domain.max_arity();
+        let constant_range = domain.constant_function().map(|_| params.constant_range);
+
+        let mut cgp_edges = CGPEdges::new(params.nbr_inputs + params.nbr_computational_nodes);
+
+        let nodes_grid = new_nodes_grid(&params, |position, node_type| {
+            NodeDAG::new(position,
+                         params.nbr_inputs,
+                         params.nbr_computational_nodes,
+                         params.nbr_rows,
+                         params.levels_back,
+                         nbr_functions,
+                         max_arity,
+                         constant_range,
+                         params.constant_sigma,
+                         node_type,
+                         &mut cgp_edges,
+                         rng,
+            )
+        });
+        let output_node_ids = output_node_ids(&params);
+
+        Self {
+            params,
+            domain,
+            nodes_grid,
+            output_node_ids,
+            active_nodes: None,
+            output_transform: D::OutputTransform::default(),
+            phenotype: Phenotype::default(),
+            cgp_edges,
+        }
+    }
+
+    fn params(&self) -> &g_params {
+        &self.params
+    }
+
+    fn phenotype(&self) -> &Phenotype<D::Value> {
+        &self.phenotype
+    }
+
+    fn domain_and_phenotype_mut(&mut self) -> (&D, &mut Phenotype<D::Value>) {
+        (&self.domain, &mut self.phenotype)
+    }
+
+    /// Connections may point to any node of the grid, so the active nodes are sorted
+    /// topologically instead of by their position.
+    fn get_active_nodes_id(&mut self) {
+        let active = get_active_nodes(&self.domain, &self.nodes_grid, &self.output_node_ids);
+
+        let mut graph = StableGraph::<usize, ()>::new();
+        let nodes: Vec<NodeIndex> = (0..self.nodes_grid.len())
+            .map(|node_id| graph.add_node(node_id))
+            .collect();
+
+        let mut is_active = vec![false; self.nodes_grid.len()];
+        for node_id in &active {
+            is_active[*node_id] = true;
+
+            let node = &self.nodes_grid[*node_id];
+            let connections = match node.node_type {
+                NodeType::InputNode => continue,
+                NodeType::ComputationalNode => &node.connections()[..self.domain.arity(node.function_id)],
+                NodeType::OutputNode => &node.connections()[..1],
+            };
+            for connection in connections {
+                graph.add_edge(nodes[*connection], nodes[*node_id], ());
+            }
+        }
+        for (node_id, node) in nodes.iter().enumerate() {
+            if !is_active[node_id] {
+                graph.remove_node(*node);
+            }
+        }
+
+        let active_nodes = toposort(&graph, None)
+            .expect("DAG genotype contains a cycle after repair")
+            .into_iter()
+            .map(|node| node.index())
+            .collect::<Vec<usize>>();
+
+        self.active_nodes = Some(active_nodes);
+    }
+
+    fn mutate_node(&mut self, node_id: usize, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        self.nodes_grid[node_id].mutate(&mut self.cgp_edges, rng);
+    }
+
+    fn mutate_gene(&mut self, node_id: usize, gene: usize, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        self.nodes_grid[node_id].mutate_gene(gene, &mut self.cgp_edges, rng);
+    }
+
+    fn reorder(&mut self, _rng: &mut ChaCha8Rng) -> bool {
+        true
+    }
+
+    /// Crossover combines nodes of different parents, whose connections may form a cycle.
+    /// The edges are rebuilt node by node and each connection closing a cycle is redirected.
+    fn repair(&mut self, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        let mut cgp_edges = CGPEdges::new(self.params.nbr_inputs + self.params.nbr_computational_nodes);
+
+        for node in self.nodes_grid
+            .iter_mut()
+            .take(self.params.nbr_inputs + self.params.nbr_computational_nodes)
+            .skip(self.params.nbr_inputs) {
+            node.add_edges_without_cycle(&mut cgp_edges, rng);
+        }
+
+        self.cgp_edges = cgp_edges;
+
+        self.get_active_nodes_id();
+    }
+
+    fn active_nodes(&self) -> &[usize] {
+        self.active_nodes.as_deref().unwrap_or(&[])
+    }
+
+    fn nodes_grid(&self) -> &[NodeDAG] {
+        &self.nodes_grid
+    }
+
+    fn nodes_grid_mut(&mut self) -> &mut [NodeDAG] {
+        self.phenotype.tape = None;
+        &mut self.nodes_grid
+    }
+
+    fn domain(&self) -> &D {
+        &self.domain
+    }
+
+    fn output_transform(&self) -> &D::OutputTransform {
+        &self.output_transform
+    }
+
+    fn set_output_transform(&mut self, output_transform: D::OutputTransform) {
+        self.output_transform = output_transform;
+    }
+
+    fn is_valid_connection(&self, node_id: usize, connection: usize) -> bool {
+        self.nodes_grid[node_id].is_valid_connection(connection)
+    }
+}
+
+impl<D: ValueDomain> Chromosome<D> {
+    fn get_dependency_graph(&self) -> StableGraph<usize, ()> {
+        let mut graph = StableGraph::<usize, ()>::new();
+
+        let mut node_indices: Vec<NodeIndex> = Vec::with_capacity(self.params.nbr_inputs + self.params.nbr_computational_nodes + self.params.nbr_outputs);
+        for i in 0..(self.params.nbr_inputs + self.params.nbr_computational_nodes + self.params.nbr_outputs) {
+            node_indices.push(graph.add_node(i));
+        }
+
+        for current_node_id in 0..(self.params.nbr_inputs + self.params.nbr_computational_nodes + self.params.nbr_outputs) {
+            let current_node: &NodeDAG = &self.nodes_grid[current_node_id];
+
+            match current_node.node_type {
+                NodeType::InputNode => continue,
+
+                NodeType::ComputationalNode => {
+                    for connection in &current_node.connections {
+                        graph.add_edge(node_indices[*connection], node_indices[current_node.position], ());
+                    }
+                }
+
+                NodeType::OutputNode => {
+                    let connection0 = current_node.connections[0];
+                    graph.add_edge(node_indices[connection0], node_indices[current_node.position], ());
+
+                }
+            }
+        }
+        graph
+    }
+
+    /// sorts and repositions the genotype into a feed-forward graph for crossover
+    pub fn unravel(&mut self) {
+        self.phenotype.tape = None;
+        self.create_new_genotype();
+
+        self.renew_graph_dependencies();
+
+        self.get_active_nodes_id();
+    }
+
+    fn get_new_locations_mapping(&mut self) -> HashMap<usize, usize, BuildNoHashHasher<usize>> {
+        //     get the new positions of a node in a grid
+
+        let mut topo_graph = self.get_dependency_graph();
+        // Remove input and output nodes from the sorting process, as they should not change
+        // position
+        for node_id in 0..self.params.nbr_inputs {
+            topo_graph.remove_node(NodeIndex::new(node_id));
+        }
+        for node_id in (self.params.nbr_computational_nodes + self.params.nbr_inputs)..(self.params.nbr_outputs + self.params.nbr_inputs + self.params.nbr_computational_nodes) {
+            topo_graph.remove_node(NodeIndex::new(node_id));
+        }
+
+        // Sort nodes
+        let sorted_node_indice = toposort(&topo_graph, None).unwrap();
+
+        // convert NodeIndex to usize
+        let sorted_node_indice = sorted_node_indice
+            .into_iter()
+            .map(|node| node.index())
+            .collect::<Vec<usize>>();
+
+        // Create a dictionary with new Locations
+        // node index -> new location
+        let mut new_location: HashMap<usize, usize, nohash_hasher::BuildNoHashHasher<usize>> = HashMap::default();
+        new_location.reserve(self.params.nbr_outputs + self.params.nbr_inputs + self.params.nbr_computational_nodes);
+
+        for (location, node_id) in sorted_node_indice.iter().enumerate() {
+            // currently: location == ranking of the nodes
+            // to get the new position:
+            // -> shift the location by nbr_inputs.
+            new_location.insert(*node_id, location + self.params.nbr_inputs);
+        }
+
+        new_location
+    }
+
+    fn create_new_genotype(&mut self) {
+        let mut new_mapping: HashMap<usize, usize, BuildNoHashHasher<usize>> = self.get_new_locations_mapping();
+
+        // input and output nodes keep their location, as the new grid is a copy of the old one
+        let mut new_nodes_grid: Vec<NodeDAG> = self.nodes_grid.clone();
+
+        // insert nodes into their new location
+        for (node_id, new_location) in &new_mapping {
+            new_nodes_grid[*new_location] = self.nodes_grid[*node_id].clone();
+        }
+
+        // update input and output nodes with same location for the mapping of nodes
+        for node_id in 0..self.params.nbr_inputs {
+            new_mapping.insert(node_id, node_id);
+        }
+        for node_id in (self.params.nbr_computational_nodes + self.params.nbr_inputs)..(self.params.nbr_outputs + self.params.nbr_inputs + self.params.nbr_computational_nodes) {
+            new_mapping.insert(node_id, node_id);
+        }
+
+        // fix the connections for computational nodes
+        for (node_id, node) in new_nodes_grid
+            .iter_mut()
+            .enumerate()
+            .take(self.params.nbr_computational_nodes + self.params.nbr_inputs)
+            .skip(self.params.nbr_inputs) {
+            for connection in node.connections.iter_mut() {
+                assert!(*new_mapping.get(connection).unwrap() < node_id);
+                *connection = *new_mapping.get(connection).unwrap();
+            }
+
+            node.position = node_id;
+        }
+
+        // fix the connections for output nodes
+        for node in new_nodes_grid.iter_mut().skip(self.params.nbr_computational_nodes + self.params.nbr_inputs) {
+            assert!(*new_mapping.get(&node.connections[0]).unwrap() < node.position);
+            node.connections[0] = *new_mapping.get(&node.connections[0]).unwrap();
+        }
+
+        self.nodes_grid = new_nodes_grid;
+    }
+
+    pub fn renew_graph_dependencies(&mut self) {
+        let mut cgp_edges = CGPEdges::new(self.params.nbr_inputs + self.params.nbr_computational_nodes);
+
+        for node_id in (self.params.nbr_inputs)..(self.params.nbr_computational_nodes + self.params.nbr_inputs) {
+            for connection in &self.nodes_grid[node_id].connections {
+                cgp_edges.add_edge(node_id, *connection);
+            }
+        }
+
+        self.cgp_edges = cgp_edges;
+    }
+}
\ No newline at end of file
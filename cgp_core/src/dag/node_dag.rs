@@ -0,0 +1,298 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use rand::distributions::Distribution;
+use rand_distr::Normal;
+use crate::genotype::CgpNode;
+use crate::utils::node_type::NodeType;
+use crate::utils::cycle_checker::CGPEdges;
+use crate::utils::utility_funcs::{gen_random_connection_in_range, levels_back_range};
+
+#[derive(Clone)]
+pub struct NodeDAG {
+    pub position: usize,
+    pub node_type: NodeType,
+    pub nbr_inputs: usize,
+    pub graph_width: usize,
+    pub nbr_rows: usize,
+    pub levels_back: usize,
+    pub nbr_functions: usize,
+    pub max_arity: usize,
+    pub function_id: usize,
+    /// Input nodes have no connections, output nodes one and computational nodes `max_arity`.
+    pub connections: Vec<usize>,
+    /// Constant gene of computational nodes, output of the constant function. `None` if the
+    /// function set has no constant.
+    pub constant: Option<f32>,
+    /// Standard deviation of the Gaussian mutation of `constant`.
+    pub constant_sigma: f32,
+}
+
+impl Display for NodeDAG {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Node Pos: {}, ", self.position)?;
+        write!(f, "Node Type: {}, ", self.node_type)?;
+        write!(f, "Function ID: {}, ", self.function_id)?;
+        write!(f, "Connections: {:?}, ", self.connections)?;
+        writeln!(f, "Constant: {:?}, ", self.constant)
+    }
+}
+
+impl CgpNode for NodeDAG {
+    fn node_type(&self) -> &NodeType {
+        &self.node_type
+    }
+
+    fn function_id(&self) -> usize {
+        self.function_id
+    }
+
+    fn set_function_id(&mut self, function_id: usize) {
+        self.function_id = function_id;
+    }
+
+    fn connections(&self) -> &[usize] {
+        &self.connections
+    }
+
+    fn connections_mut(&mut self) -> &mut [usize] {
+        &mut self.connections
+    }
+
+    fn constant(&self) -> Option<f32> {
+        self.constant
+    }
+
+    fn set_constant(&mut self, constant: Option<f32>) {
+        self.constant = constant;
+    }
+}
+
+impl NodeDAG {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(position: usize,
+               nbr_inputs: usize,
+               graph_width: usize,
+               nbr_rows: usize,
+               levels_back: usize,
+               nbr_functions: usize,
+               max_arity: usize,
+               constant_range: Option<f32>,
+               constant_sigma: f32,
+               node_type: NodeType,
+               cgp_edges: &mut CGPEdges,
+               rng: &mut ChaCha8Rng,
+    ) -> Self {
+        let function_id = rng.gen_range(0..nbr_functions);
+        let connections: Vec<usize> = match node_type {
+            NodeType::InputNode => vec![],
+            NodeType::ComputationalNode => {
+                // start feed-forward, so the initial graph is acyclic
+                let connections: Vec<usize> = (0..max_arity)
+                    .map(|_| gen_random_connection_in_range(usize::MAX,
+                                                            nbr_inputs,
+                                                            levels_back_range(position, nbr_inputs, nbr_rows, levels_back),
+                                                            rng))
+                    .collect();
+                for connection in &connections {
+                    cgp_edges.add_edge(position, *connection);
+                }
+                connections
+            }
+            NodeType::OutputNode => vec![rng.gen_range(0..nbr_inputs + graph_width)],
+        };
+
+        // ephemeral random constant; only drawn if the function set has a constant
+        let constant = match (&node_type, constant_range) {
+            (NodeType::ComputationalNode, Some(constant_range)) => Some(rng.gen_range(-constant_range..=constant_range)),
+            _ => None,
+        };
+
+        Self {
+            position,
+            node_type,
+            nbr_inputs,
+            graph_width,
+            nbr_rows,
+            levels_back,
+            nbr_functions,
+            max_arity,
+            function_id,
+            connections,
+            constant,
+            constant_sigma,
+        }
+    }
+
+    pub fn mutate(&mut self, cgp_edges: &mut CGPEdges, rng: &mut ChaCha8Rng) {
+        match self.node_type {
+            NodeType::OutputNode => self.mutate_output_node(rng),
+            NodeType::ComputationalNode => {
+                // one gene per connection plus the function gene and the constant gene
+                let gene = rng.gen_range(0..self.nbr_genes());
+                self.mutate_computational_node(gene, cgp_edges, rng)
+            }
+            _ => { panic!("Trying to mutate input node") }
+        }
+    }
+
+    /// Mutates gene `gene`; ids `0..max_arity` are the connections, `max_arity` is the function
+    /// and `max_arity + 1` the constant.
+    pub fn mutate_gene(&mut self, gene: usize, cgp_edges: &mut CGPEdges, rng: &mut ChaCha8Rng) {
+        match self.node_type {
+            NodeType::OutputNode => self.mutate_output_node(rng),
+            NodeType::ComputationalNode => self.mutate_computational_node(gene, cgp_edges, rng),
+            _ => { panic!("Trying to mutate input node") }
+        }
+    }
+
+    /// Adds all connections of the node to `cgp_edges`. A connection that would close a
+    /// cycle is redirected to a random node that does not.
+    pub fn add_edges_without_cycle(&mut self, cgp_edges: &mut CGPEdges, rng: &mut ChaCha8Rng) {
+        let position = self.position;
+        let nbr_inputs = self.nbr_inputs;
+        let allowed = self.connection_range();
+
+        for connection in self.connections.iter_mut() {
+            if *connection == position || cgp_edges.leads_to_cycle(position, *connection) {
+                *connection = gen_random_connection(*connection,
+                                                    position,
+                                                    nbr_inputs,
+                                                    allowed.clone(),
+                                                    cgp_edges,
+                                                    rng);
+            }
+            cgp_edges.add_edge(position, *connection);
+        }
+    }
+
+    /// Candidates are the program inputs and the computational nodes in `allowed`.
+    fn mutate_connection(connection: &mut usize, position: usize, nbr_inputs: usize, allowed: Range<usize>, cgp_edges: &mut CGPEdges, rng: &mut ChaCha8Rng) {
+        let new_connection_id = gen_random_connection(*connection,
+                                                      position,
+                                                      nbr_inputs,
+                                                      allowed,
+                                                      cgp_edges,
+                                                      rng);
+        cgp_edges.remove_edge(position, *connection);
+        cgp_edges.add_edge(position, new_connection_id);
+        *connection = new_connection_id;
+    }
+
+    /// Computational nodes this node may connect to. As connections may point forward in a DAG,
+    /// `levels_back` limits the column distance in both directions.
+    fn connection_range(&self) -> Range<usize> {
+        let nbr_columns = self.graph_width / self.nbr_rows;
+        let column = (self.position - self.nbr_inputs) / self.nbr_rows;
+        let first_column = column.saturating_sub(self.levels_back);
+        let last_column = (column + self.levels_back).min(nbr_columns - 1);
+
+        (self.nbr_inputs + first_column * self.nbr_rows)..(self.nbr_inputs + (last_column + 1) * self.nbr_rows)
+    }
+
+    /// True if this node may read from `connection`, ignoring cycles.
+    pub fn is_valid_connection(&self, connection: usize) -> bool {
+        connection != self.position
+            && (connection < self.nbr_inputs || self.connection_range().contains(&connection))
+    }
+
+    /// Gaussian mutation of the constant gene.
+    fn mutate_constant(&mut self, rng: &mut ChaCha8Rng) {
+        let constant = self.constant.as_mut().expect("Trying to mutate a missing constant");
+        *constant += Normal::new(0., self.constant_sigma).unwrap().sample(rng);
+    }
+
+    fn mutate_function(&mut self, rng: &mut ChaCha8Rng) {
+        self.function_id = gen_random_function_id(self.function_id, self.nbr_functions, rng);
+    }
+
+    fn mutate_output_node(&mut self, rng: &mut ChaCha8Rng) {
+        loop {
+            let rand_nbr: usize = rng.gen_range(0..self.nbr_inputs + self.graph_width);
+
+            if rand_nbr != self.connections[0] {
+                self.connections[0] = rand_nbr;
+                break;
+            }
+        }
+    }
+
+    fn mutate_computational_node(&mut self, gene: usize, cgp_edges: &mut CGPEdges, rng: &mut ChaCha8Rng) {
+        if gene < self.max_arity {
+            let allowed = self.connection_range();
+            NodeDAG::mutate_connection(&mut self.connections[gene],
+                                       self.position,
+                                       self.nbr_inputs,
+                                       allowed,
+                                       cgp_edges,
+                                       rng);
+        } else if gene == self.max_arity {
+            self.mutate_function(rng);
+        } else {
+            self.mutate_constant(rng);
+        }
+    }
+}
+
+
+fn gen_random_function_id(excluded: usize, upper_range: usize, rng: &mut ChaCha8Rng) -> usize {
+    if upper_range <= 1 {
+        return excluded;
+    }
+    loop {
+        let rand_nbr: usize = rng.gen_range(0..upper_range);
+        if rand_nbr != excluded {
+            return rand_nbr;
+        }
+    }
+}
+
+/// A random connection of the node at `position` to one of the program inputs or the nodes in
+/// `allowed` that differs from `previous_connection` and does not close a cycle. Keeps
+/// `previous_connection` if there is no such node. Inputs never close a cycle, so a connection
+/// that closes one is always replaced.
+fn gen_random_connection(previous_connection: usize, position: usize, nbr_inputs: usize, allowed: Range<usize>, cgp_edges: &mut CGPEdges, rng: &mut ChaCha8Rng) -> usize {
+    let mut candidates: Vec<usize> = (0..nbr_inputs)
+        .chain(allowed)
+        .filter(|candidate| *candidate != previous_connection && *candidate != position)
+        .collect();
+    // the cycle check is expensive, so candidates are only checked until one is found
+    candidates.shuffle(rng);
+
+    candidates.into_iter()
+        .find(|candidate| !cgp_edges.leads_to_cycle(position, *candidate))
+        .unwrap_or(previous_connection)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+    use super::*;
+
+    #[test]
+    fn gen_random_connection_avoids_cycles() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        // 2 inputs, nodes 2 -> 3 -> 4, so node 2 must not read from 3 or 4
+        let mut cgp_edges = CGPEdges::new(5);
+        cgp_edges.add_edge(3, 2);
+        cgp_edges.add_edge(4, 3);
+
+        for _ in 0..100 {
+            let connection = gen_random_connection(4, 2, 2, 2..5, &mut cgp_edges, &mut rng);
+            assert!(connection < 2, "connection {} closes a cycle", connection);
+        }
+    }
+
+    #[test]
+    fn gen_random_connection_keeps_connection_without_candidates() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        // a single input, which node 1 already reads, and node 2 reads node 1
+        let mut cgp_edges = CGPEdges::new(3);
+        cgp_edges.add_edge(1, 0);
+        cgp_edges.add_edge(2, 1);
+
+        assert_eq!(gen_random_connection(0, 1, 1, 1..3, &mut cgp_edges, &mut rng), 0);
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use nohash_hasher::BuildNoHashHasher;
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use crate::global_params::CgpParameters as g_params;
+use crate::utils::crossover::semantic_operators;
+use crate::utils::evaluation_buffer::{ColumnStore, DatasetId, EvaluationBuffer};
+use crate::utils::node_type::NodeType;
+use crate::utils::tape::Tape;
+use crate::value_domain::ValueDomain;
+
+/// Read access to the genes of a node, shared by the node types of all CGP variants.
+pub trait CgpNode: Clone {
+    fn node_type(&self) -> &NodeType;
+
+    fn function_id(&self) -> usize;
+
+    fn set_function_id(&mut self, function_id: usize);
+
+    /// Input nodes have no connections, output nodes one and computational nodes `max_arity`.
+    fn connections(&self) -> &[usize];
+
+    fn connections_mut(&mut self) -> &mut [usize];
+
+    /// Constant gene of computational nodes. `None` if the function set has no constant.
+    fn constant(&self) -> Option<f32>;
+
+    fn set_constant(&mut self, constant: Option<f32>);
+
+    /// Number of mutable genes: one connection for output nodes, `max_arity` connections plus
+    /// the function and, if present, the constant for computational nodes.
+    fn nbr_genes(&self) -> usize {
+        match self.node_type() {
+            NodeType::InputNode => 0,
+            NodeType::OutputNode => 1,
+            NodeType::ComputationalNode => self.connections().len() + 1 + self.constant().is_some() as usize,
+        }
+    }
+}
+
+/// What a chromosome derives from its genes for evaluation: the compiled active graph and the
+/// columns of its last evaluation.
+#[derive(Clone, Default)]
+pub struct Phenotype<V> {
+    /// Compiled active graph; `None` after every change of the genes until the next evaluation.
+    pub tape: Option<Tape>,
+    /// Registers of the tape, reused between evaluations. Refers to columns of the
+    /// `ColumnStore` of the runner.
+    pub evaluation_buffer: EvaluationBuffer<V>,
+}
+
+/// The CGP variant of a chromosome.
+///
+/// Implemented by the standard CGP chromosome, CGP with E-Reorder and DAG-CGP. Runners and
+/// crossover operators are generic over this trait, so the variant is chosen at runtime
+/// via `--cgp-type` instead of a cargo feature.
+pub trait Genotype<D: ValueDomain>: Clone + Display {
+    type Node: CgpNode;
+
+    fn new(params: g_params, domain: D, rng: &mut ChaCha8Rng) -> Self;
+
+    fn params(&self) -> &g_params;
+
+    fn phenotype(&self) -> &Phenotype<D::Value>;
+
+    /// The domain and the phenotype at once, so the phenotype can be evaluated in the domain.
+    fn domain_and_phenotype_mut(&mut self) -> (&D, &mut Phenotype<D::Value>);
+
+    /// The active graph compiled into a register program. Compiled first if the genes changed
+    /// since the last compilation.
+    fn tape(&mut self) -> &Tape {
+        // the genes did not change since the last compilation, so neither did the active nodes
+        if self.phenotype().tape.is_none() {
+            self.get_active_nodes_id();
+            let tape = Tape::compile(self.domain(), self.nodes_grid(), self.active_nodes());
+            self.domain_and_phenotype_mut().1.tape = Some(tape);
+        }
+        self.phenotype().tape.as_ref().unwrap()
+    }
+
+    /// Canonical hash of the active graph; equal for chromosomes computing the same expressions.
+    fn phenotype_hash(&mut self) -> u64 {
+        self.tape().hash
+    }
+
+    /// Computes the output columns of all active nodes on `inputs` into `column_store`. The
+    /// active graph is compiled into a `Tape` once per change of the genes, later calls only
+    /// run the tape. Columns of the last evaluation on `dataset` whose expression did not
+    /// change are not computed again, if they were not freed in the meantime.
+    fn evaluate_nodes(&mut self, inputs: &[Vec<D::Value>], dataset: DatasetId, column_store: &mut ColumnStore<D::Value>) {
+        self.tape();
+
+        let (domain, phenotype) = self.domain_and_phenotype_mut();
+        phenotype.evaluation_buffer.run(domain, phenotype.tape.as_ref().unwrap(), inputs, dataset, column_store);
+    }
+
+    /// Columns of the output nodes, as computed by the last `evaluate_nodes` on `inputs`.
+    fn output_columns<'a>(&self, inputs: &'a [Vec<D::Value>], column_store: &'a ColumnStore<D::Value>) -> Vec<&'a [D::Value]> {
+        let phenotype = self.phenotype();
+        let tape = phenotype.tape.as_ref().expect("output_columns is only valid after evaluate_nodes");
+        phenotype.evaluation_buffer.output_columns(tape, inputs, column_store)
+    }
+
+    /// Computes the active nodes and returns the columns of the output nodes.
+    fn predict(&mut self, inputs: &[Vec<D::Value>], dataset: DatasetId, column_store: &mut ColumnStore<D::Value>) -> Vec<Vec<D::Value>> {
+        self.evaluate_nodes(inputs, dataset, column_store);
+
+        self.output_columns(inputs, column_store)
+            .iter()
+            .map(|output| output.to_vec())
+            .collect()
+    }
+
+    /// Computes the active nodes and returns the fitness of the chromosome on the training
+    /// data. Fits the output transform of the domain, e.g. linear scaling, to `labels`.
+    fn evaluate(&mut self, inputs: &[Vec<D::Value>], labels: &[Vec<D::Value>], column_store: &mut ColumnStore<D::Value>) -> f32 {
+        self.evaluate_nodes(inputs, DatasetId::Training, column_store);
+        let outputs = self.output_columns(inputs, column_store);
+
+        let output_transform = self.domain().fit_output_transform(&outputs, labels);
+        let fitness = self.domain().transformed_fitness(&outputs, labels, &output_transform);
+        self.set_output_transform(output_transform);
+        fitness
+    }
+
+    /// Fitness on unseen data, using the output transform fitted by the last `evaluate`.
+    fn evaluate_test(&mut self, inputs: &[Vec<D::Value>], labels: &[Vec<D::Value>], column_store: &mut ColumnStore<D::Value>) -> f32 {
+        self.evaluate_nodes(inputs, DatasetId::Evaluation, column_store);
+        let outputs = self.output_columns(inputs, column_store);
+
+        self.domain().transformed_fitness(&outputs, labels, self.output_transform())
+    }
+
+    /// Mutates a random gene of the node `node_id`. Marks the compiled tape as outdated.
+    fn mutate_node(&mut self, node_id: usize, rng: &mut ChaCha8Rng);
+
+    /// Mutates gene `gene` of the node `node_id`, see `CgpNode::nbr_genes`. Marks the compiled
+    /// tape as outdated.
+    fn mutate_gene(&mut self, node_id: usize, gene: usize, rng: &mut ChaCha8Rng);
+
+    /// Goldman's single active mutation: mutates until an active node was changed.
+    fn mutate_single(&mut self, rng: &mut ChaCha8Rng) {
+        let between = Uniform::from(mutable_node_ids(self.params()));
+        assert!(!self.active_nodes().is_empty(), "mutate_single is only valid after an evaluation");
+
+        loop {
+            let random_node_id = between.sample(rng);
+            self.mutate_node(random_node_id, rng);
+
+            if self.active_nodes().contains(&random_node_id) {
+                break;
+            }
+        }
+    }
+
+    /// Point mutation: every gene is mutated with probability `mutation_rate`.
+    fn mutate_prob(&mut self, mutation_rate: f32, rng: &mut ChaCha8Rng) {
+        for node_id in mutable_node_ids(self.params()) {
+            for gene in 0..self.nodes_grid()[node_id].nbr_genes() {
+                if rng.gen::<f32>() < mutation_rate {
+                    self.mutate_gene(node_id, gene, rng);
+                }
+            }
+        }
+    }
+
+    /// Generalisation of `mutate_single`: mutates until `k` genes of active nodes were changed.
+    fn mutate_k_active(&mut self, k: usize, rng: &mut ChaCha8Rng) {
+        let between = Uniform::from(mutable_node_ids(self.params()));
+        assert!(!self.active_nodes().is_empty(), "mutate_k_active is only valid after an evaluation");
+
+        let mut nbr_active_mutations: usize = 0;
+        while nbr_active_mutations < k {
+            let random_node_id = between.sample(rng);
+            self.mutate_node(random_node_id, rng);
+
+            if self.active_nodes().contains(&random_node_id) {
+                nbr_active_mutations += 1;
+            }
+        }
+    }
+
+    /// Applies the mutation operator selected by `params.mutation_type`. Returns false if
+    /// semantic mutation fell back to single active mutation.
+    fn mutate(&mut self, params: &g_params, rng: &mut ChaCha8Rng) -> bool {
+        match params.mutation_type {
+            0 => self.mutate_single(rng),
+            1 => self.mutate_prob(params.mutation_rate, rng),
+            2 => self.mutate_k_active(params.mutation_k, rng),
+            3 => return semantic_operators::semantic_mutation(self, params, rng),
+            _ => panic!("Wrong mutation type"),
+        }
+        true
+    }
+
+    /// Repositions the nodes of the genotype. Returns false if the nodes were kept in place,
+    /// because the new positions would violate the levels-back limit. Does nothing and returns
+    /// true for variants without reordering.
+    fn reorder(&mut self, rng: &mut ChaCha8Rng) -> bool;
+
+    /// Recomputes the active nodes.
+    fn get_active_nodes_id(&mut self);
+
+    /// Must be called after the nodes grid was changed from outside, e.g. by crossover.
+    /// Restores the invariants of the variant and recomputes the active nodes.
+    fn repair(&mut self, _rng: &mut ChaCha8Rng) {
+        self.get_active_nodes_id();
+    }
+
+    /// Active nodes, sorted in evaluation order. Empty before the first evaluation.
+    fn active_nodes(&self) -> &[usize];
+
+    fn nodes_grid(&self) -> &[Self::Node];
+
+    /// Marks the compiled tape as outdated, so changes are picked up by the next evaluation.
+    fn nodes_grid_mut(&mut self) -> &mut [Self::Node];
+
+    fn domain(&self) -> &D;
+
+    fn output_transform(&self) -> &D::OutputTransform;
+
+    fn set_output_transform(&mut self, output_transform: D::OutputTransform);
+
+    /// True if the computational node `node_id` may read from `connection` in this variant.
+    fn is_valid_connection(&self, node_id: usize, connection: usize) -> bool;
+
+    /// The active graph in evaluation order with one assignment per node, e.g. `n5 = (x0 + x1)`.
+    /// Inputs are named `x<i>`, computational nodes `n<position>` and outputs `y<i>`.
+    fn active_program(&self) -> String {
+        Tape::compile(self.domain(), self.nodes_grid(), self.active_nodes())
+            .display(self.domain(), self.output_transform())
+    }
+}
+
+/// Ids of the nodes chosen by the mutation operators: all computational and output nodes.
+fn mutable_node_ids(params: &g_params) -> std::ops::Range<usize> {
+    // Serious edge case: with a single input, the first computational node can only connect
+    // to input 0, so a mutation forced to change its connection would not terminate.
+    let start_id = params.nbr_inputs.max(2);
+    start_id..params.nbr_inputs + params.nbr_computational_nodes + params.nbr_outputs
+}
+
+/// The nodes grid of a new chromosome: input nodes, computational nodes and output nodes in this
+/// order. `new_node` creates the node of a position.
+pub fn new_nodes_grid<N: CgpNode>(params: &g_params, mut new_node: impl FnMut(usize, NodeType) -> N) -> Vec<N> {
+    let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+    let mut nodes_grid: Vec<N> = Vec::with_capacity(first_output_id + params.nbr_outputs);
+
+    for position in 0..first_output_id + params.nbr_outputs {
+        let node_type = if position < params.nbr_inputs {
+            NodeType::InputNode
+        } else if position < first_output_id {
+            NodeType::ComputationalNode
+        } else {
+            NodeType::OutputNode
+        };
+        nodes_grid.push(new_node(position, node_type));
+    }
+    nodes_grid
+}
+
+/// Ids of the output nodes, which are the last nodes of the grid.
+pub fn output_node_ids(params: &g_params) -> Vec<usize> {
+    let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+    (first_output_id..first_output_id + params.nbr_outputs).collect()
+}
+
+/// Ids of the nodes the output nodes depend on, including the inputs and the output nodes, in
+/// ascending order. Only the connections a function reads are followed.
+pub fn get_active_nodes<D: ValueDomain, N: CgpNode>(domain: &D, nodes_grid: &[N], output_node_ids: &[usize]) -> Vec<usize> {
+    let mut active: HashSet<usize, BuildNoHashHasher<usize>> = HashSet::with_capacity_and_hasher(
+        nodes_grid.len(),
+        BuildNoHashHasher::default(),
+    );
+
+    let mut to_visit: Vec<usize> = Vec::with_capacity(nodes_grid.len());
+
+    for output_node_id in output_node_ids {
+        active.insert(*output_node_id);
+        to_visit.push(*output_node_id);
+    }
+
+    while let Some(current_node_id) = to_visit.pop() {
+        let current_node = &nodes_grid[current_node_id];
+
+        let connections = match current_node.node_type() {
+            NodeType::InputNode => continue,
+            NodeType::ComputationalNode => &current_node.connections()[..domain.arity(current_node.function_id())],
+            NodeType::OutputNode => &current_node.connections()[..1],
+        };
+        for connection in connections {
+            if active.insert(*connection) {
+                to_visit.push(*connection);
+            }
+        }
+    }
+
+    let mut active: Vec<usize> = active.into_iter().collect();
+    active.sort_unstable();
+    active
+}
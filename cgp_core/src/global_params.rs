@@ -0,0 +1,102 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone)]
+pub struct CgpParameters {
+    pub nbr_computational_nodes: usize,
+    /// The computational nodes form a `nbr_rows x nbr_columns` grid, stored column by column.
+    pub nbr_rows: usize,
+    pub nbr_columns: usize,
+    /// Number of preceding columns a computational node may connect to.
+    pub levels_back: usize,
+    pub population_size: usize,
+    pub mu: usize,
+    pub lambda: usize,
+    pub eval_after_iterations: usize,
+    pub nbr_inputs: usize,
+    pub nbr_outputs: usize,
+    pub mutation_type: usize,
+    pub mutation_rate: f32,
+    pub mutation_k: usize,
+    /// Ephemeral random constants are drawn from `[-constant_range, constant_range]`.
+    pub constant_range: f32,
+    /// Standard deviation of the Gaussian mutation of constants.
+    pub constant_sigma: f32,
+    /// The constants of the elitists are tuned every `constant_optimisation_interval`
+    /// generations; 0 disables the tuning.
+    pub constant_optimisation_interval: usize,
+    /// Maximum number of evaluations per tuned elitist.
+    pub constant_optimisation_evaluations: usize,
+    /// The tuning stops after this many evaluations on the training data in total; 0: no limit.
+    pub max_evaluations: usize,
+    pub crossover_type: usize,
+    pub crossover_rate: f32,
+    /// Lets point, multi-point and uniform crossover exchange output nodes as well.
+    pub crossover_include_outputs: bool,
+    pub tournament_size: usize,
+    pub elitism_number: usize,
+    pub multi_point_n: usize,
+    pub cgp_type: usize,
+    pub seed: u64,
+}
+
+impl Default for CgpParameters {
+    fn default() -> Self {
+        CgpParameters {
+            nbr_computational_nodes: 0,
+            nbr_rows: 1,
+            nbr_columns: 0,
+            levels_back: 0,
+            population_size: 0,
+            mu: 1,
+            lambda: 4,
+            eval_after_iterations: 500,
+            nbr_inputs: 0,
+            nbr_outputs: 0,
+            mutation_type: 0,
+            mutation_rate: -1.0,
+            mutation_k: 1,
+            constant_range: 1.0,
+            constant_sigma: 0.1,
+            constant_optimisation_interval: 0,
+            constant_optimisation_evaluations: 100,
+            max_evaluations: 0,
+            crossover_type: 0,
+            crossover_rate: -1.0,
+            crossover_include_outputs: false,
+            tournament_size: 0,
+            elitism_number: 0,
+            multi_point_n: 0,
+            cgp_type: 0,
+            seed: 0,
+        }
+    }
+}
+
+impl Display for CgpParameters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "############ Parameters ############")?;
+        writeln!(f, "graph_width: {}", self.nbr_computational_nodes)?;
+        writeln!(f, "nbr_rows: {}", self.nbr_rows)?;
+        writeln!(f, "nbr_columns: {}", self.nbr_columns)?;
+        writeln!(f, "levels_back: {}", self.levels_back)?;
+        writeln!(f, "mu: {}", self.mu)?;
+        writeln!(f, "lambda: {}", self.lambda)?;
+        writeln!(f, "eval_after_iterations: {}", self.eval_after_iterations)?;
+        writeln!(f, "nbr_inputs: {}", self.nbr_inputs)?;
+        writeln!(f, "nbr_outputs: {}", self.nbr_outputs)?;
+        writeln!(f, "mutation_type: {}", self.mutation_type)?;
+        writeln!(f, "mutation_rate: {}", self.mutation_rate)?;
+        writeln!(f, "mutation_k: {}", self.mutation_k)?;
+        writeln!(f, "constant_range: {}", self.constant_range)?;
+        writeln!(f, "constant_sigma: {}", self.constant_sigma)?;
+        writeln!(f, "constant_optimisation_interval: {}", self.constant_optimisation_interval)?;
+        writeln!(f, "constant_optimisation_evaluations: {}", self.constant_optimisation_evaluations)?;
+        writeln!(f, "max_evaluations: {}", self.max_evaluations)?;
+        writeln!(f, "crossover_type: {}", self.crossover_type)?;
+        writeln!(f, "crossover_rate: {}", self.crossover_rate)?;
+        writeln!(f, "crossover_include_outputs: {}", self.crossover_include_outputs)?;
+        writeln!(f, "multi_point_n: {}", self.multi_point_n)?;
+        writeln!(f, "seed: {}", self.seed)?;
+        writeln!(f, "#########################")
+    }
+}
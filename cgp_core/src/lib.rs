@@ -0,0 +1,7 @@
+pub mod utils;
+pub mod global_params;
+pub mod value_domain;
+pub mod genotype;
+pub mod standard_cgp;
+pub mod dag;
+pub mod reorder;
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use nohash_hasher::BuildNoHashHasher;
+use rand_chacha::ChaCha8Rng;
+use crate::genotype::{get_active_nodes, new_nodes_grid, output_node_ids, Genotype, Phenotype};
+use crate::global_params::CgpParameters as g_params;
+use crate::reorder::node_reorder::NodeReorder;
+use crate::utils::utility_funcs;
+use crate::reorder::linspace::linspace;
+use crate::value_domain::ValueDomain;
+
+#[derive(Clone)]
+pub struct Chromosome<D: ValueDomain> {
+    pub params: g_params,
+    pub domain: D,
+    pub nodes_grid: Vec<NodeReorder>,
+    pub output_node_ids: Vec<usize>,
+    pub active_nodes: Option<Vec<usize>>,
+    /// Fitted to the training data by the last call of `evaluate`.
+    pub output_transform: D::OutputTransform,
+    pub phenotype: Phenotype<D::Value>,
+}
+
+impl<D: ValueDomain> Display for Chromosome<D> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "+++++++++++++++++ Chromosome +++++++++++")?;
+        writeln!(f, "Nodes:")?;
+        for node in &self.nodes_grid {
+            write!(f, "{}", *node)?;
+        }
+        writeln!(f, "Active_nodes: {:?}", self.active_nodes)?;
+        writeln!(f, "Output_nodes: {:?}", self.output_node_ids)
+    }
+}
+
+impl<D: ValueDomain> Genotype<D> for Chromosome<D> {
+    type Node = NodeReorder;
+
+    fn new(params: g_params, domain: D, rng: &mut ChaCha8Rng) -> Self {
+        let nbr_functions = domain.nbr_functions();
+        let max_arity = domain.max_arity();
+        let constant_range = domain.constant_function().map(|_| params.constant_range);
+
+        let nodes_grid = new_nodes_grid(&params, |position, node_type| {
+            NodeReorder::new(position,
+                             params.nbr_inputs,
+                             params.nbr_computational_nodes,
+                             params.nbr_rows,
+                             params.levels_back,
+                             nbr_functions,
+                             max_arity,
+                             constant_range,
+                             params.constant_sigma,
+                             node_type,
+                             rng,
+            )
+        });
+        let output_node_ids = output_node_ids(&params);
+
+        Self {
+            params,
+            domain,
+            nodes_grid,
+            output_node_ids,
+            active_nodes: None,
+            output_transform: D::OutputTransform::default(),
+            phenotype: Phenotype::default(),
+        }
+    }
+
+    fn params(&self) -> &g_params {
+        &self.params
+    }
+
+    fn phenotype(&self) -> &Phenotype<D::Value> {
+        &self.phenotype
+    }
+
+    fn domain_and_phenotype_mut(&mut self) -> (&D, &mut Phenotype<D::Value>) {
+        (&self.domain, &mut self.phenotype)
+    }
+
+    fn get_active_nodes_id(&mut self) {
+        self.active_nodes = Some(get_active_nodes(&self.domain, &self.nodes_grid, &self.output_node_ids));
+    }
+
+    fn mutate_node(&mut self, node_id: usize, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        self.nodes_grid[node_id].mutate(rng);
+    }
+
+    fn mutate_gene(&mut self, node_id: usize, gene: usize, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        self.nodes_grid[node_id].mutate_gene(gene, rng);
+    }
+
+    fn reorder(&mut self, rng: &mut ChaCha8Rng) -> bool {
+        self.phenotype.tape = None;
+        let mut c_active_nodes = self.active_nodes.clone();
+
+        // remove output nodes
+        for output_node_id in (
+            (self.params.nbr_inputs + self.params.nbr_computational_nodes)
+                ..
+                (self.params.nbr_inputs + self.params.nbr_computational_nodes + self.params.nbr_outputs)
+        ).rev()
+        {
+            let index = c_active_nodes
+                .as_ref()
+                .unwrap()
+                .iter()
+                .position(|x| *x == output_node_id)
+                .unwrap();
+            c_active_nodes.as_mut().unwrap().remove(index);
+        }
+
+        // remove input nodes, as only computational nodes are going to be swapped
+        for input_node_id in (0..self.params.nbr_inputs).rev() {
+            let index = c_active_nodes
+                .as_ref()
+                .unwrap()
+                .iter()
+                .position(|x| *x == input_node_id);
+            // if index.is_some() {
+            //     c_active_nodes.as_mut().unwrap().remove(index.unwrap());
+            // }
+            if let Some(idx) = index {
+                c_active_nodes.as_mut().unwrap().remove(idx);
+            }
+        }
+
+        if c_active_nodes.as_ref().unwrap().is_empty() {
+            return true;
+        }
+
+        self.swap_nodes(&mut c_active_nodes, rng)
+    }
+
+    fn active_nodes(&self) -> &[usize] {
+        self.active_nodes.as_deref().unwrap_or(&[])
+    }
+
+    fn nodes_grid(&self) -> &[NodeReorder] {
+        &self.nodes_grid
+    }
+
+    fn nodes_grid_mut(&mut self) -> &mut [NodeReorder] {
+        self.phenotype.tape = None;
+        &mut self.nodes_grid
+    }
+
+    fn domain(&self) -> &D {
+        &self.domain
+    }
+
+    fn output_transform(&self) -> &D::OutputTransform {
+        &self.output_transform
+    }
+
+    fn set_output_transform(&mut self, output_transform: D::OutputTransform) {
+        self.output_transform = output_transform;
+    }
+
+    fn is_valid_connection(&self, node_id: usize, connection: usize) -> bool {
+        self.nodes_grid[node_id].is_valid_connection(connection, node_id)
+    }
+}
+
+impl<D: ValueDomain> Chromosome<D> {
+    /// Places the active computational nodes equidistantly and the inactive ones into the
+    /// remaining positions. Returns false and keeps the genotype if that would move a node out
+    /// of the levels-back range of one of its inputs.
+    fn swap_nodes(&mut self, c_active_nodes: &mut Option<Vec<usize>>, rng: &mut ChaCha8Rng) -> bool {
+        let new_pos_active: Vec<usize> = linspace(self.params.nbr_inputs,
+                                                  self.params.nbr_inputs + self.params.nbr_computational_nodes - 1,
+                                                  c_active_nodes.as_ref().unwrap().len());
+
+        let comp_nodes_ids: Vec<usize> = (self.params.nbr_inputs..self.params.nbr_inputs + self.params.nbr_computational_nodes)
+            .collect();
+        let mut old_pos_inactive = utility_funcs::vect_difference(&comp_nodes_ids, c_active_nodes.as_ref().unwrap());
+        let mut new_pos_inactive = utility_funcs::vect_difference(&comp_nodes_ids, &new_pos_active);
+
+        old_pos_inactive.sort_unstable();
+        new_pos_inactive.sort_unstable();
+
+        assert_eq!(c_active_nodes.as_ref().unwrap().len(), new_pos_active.len());
+        assert_eq!(old_pos_inactive.len(),
+                   new_pos_inactive.len(),
+                   "actives: \n{:?} \n{:?}", c_active_nodes.as_ref().unwrap(), new_pos_active);
+
+        let mut swapped_pos_indices: HashMap<usize, usize, BuildNoHashHasher<usize>> = HashMap::default();
+        swapped_pos_indices.reserve(self.params.nbr_inputs + self.params.nbr_computational_nodes + self.params.nbr_outputs);
+
+
+        // Nodes are not swapped because that could destroy ordering
+        // Instead, create a new node_list by cloning the old one
+        let mut new_nodes_grid: Vec<NodeReorder> = self.nodes_grid.clone();
+
+        // Input nodes are ignored, as they do not change
+        // Insert active computational nodes and change their position
+        for (old_node_id, new_node_id) in c_active_nodes.as_ref().unwrap()
+            .iter()
+            .zip(new_pos_active.iter()) {
+            let mut node = self.nodes_grid[*old_node_id].clone();
+            node.set_new_position(*new_node_id, false, rng);
+
+            new_nodes_grid[*new_node_id] = node;
+
+            swapped_pos_indices.insert(*old_node_id, *new_node_id);
+        }
+
+        // Now distribute all inactive nodes to the free indice
+        for (old_node_id, new_node_id) in old_pos_inactive.iter().zip(new_pos_inactive.iter()) {
+            assert!(!new_pos_active.contains(new_node_id));
+
+            let mut node = self.nodes_grid[*old_node_id].clone();
+            node.set_new_position(*new_node_id, true, rng);
+            new_nodes_grid[*new_node_id] = node;
+
+            assert!(new_nodes_grid[*new_node_id].connections.iter().all(|&c| new_nodes_grid[*new_node_id].is_valid_connection(c, *new_node_id)), "assert 2 for node: {}", *new_node_id);
+        }
+
+
+        // update connections of active nodes
+        for node_id in &new_pos_active {
+            Self::update_connections(&mut new_nodes_grid, *node_id, &mut swapped_pos_indices);
+        }
+
+        // With a restricted levels-back, the equidistant positions can move a node too far
+        // away from its inputs. Keep the old genotype then, as the phenotype must not change.
+        // The runners count these skips.
+        let violates_levels_back = new_pos_active.iter().any(|node_id| {
+            let node = &new_nodes_grid[*node_id];
+            node.connections[..self.domain.arity(node.function_id)]
+                .iter()
+                .any(|connection| !node.is_valid_connection(*connection, *node_id))
+        });
+        if violates_levels_back {
+            return false;
+        }
+
+        // connections beyond the arity of the function are not remapped, as they may
+        // point to inactive nodes. Redraw those that are no longer valid.
+        for node_id in &new_pos_active {
+            new_nodes_grid[*node_id].set_new_position(*node_id, true, rng);
+        }
+
+        // update connections for output nodes
+        for node_id in (self.params.nbr_inputs + self.params.nbr_computational_nodes)..(self.params.nbr_inputs + self.params.nbr_computational_nodes + self.params.nbr_outputs) {
+            Self::update_connections(&mut new_nodes_grid, node_id, &mut swapped_pos_indices);
+        }
+
+        self.nodes_grid = new_nodes_grid;
+
+        self.get_active_nodes_id();
+        true
+    }
+
+    fn update_connections(new_nodes_grid: &mut [NodeReorder],
+                          node_id: usize,
+                          swapped_pos_indices: &mut HashMap<usize,
+                              usize,
+                              BuildNoHashHasher<usize>>) {
+        for connection in new_nodes_grid[node_id].connections.iter_mut() {
+            *connection = *swapped_pos_indices.get(connection)
+                .unwrap_or(connection);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+    use crate::utils::evaluation_buffer::{ColumnStore, DatasetId};
+    use crate::utils::node_type::NodeType;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain};
+    use crate::utils::utility_funcs::levels_back_range;
+    use super::*;
+
+    #[test]
+    fn reorder_respects_levels_back() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let params = test_params(2, 2, 40, 2, 3);
+        let inputs = test_inputs(2, 5);
+        let mut column_store = ColumnStore::default();
+
+        let mut nbr_reordered = 0;
+        for _ in 0..200 {
+            let mut chromosome = Chromosome::new(params.clone(), TestDomain { with_constant: false }, &mut rng);
+            let outputs = chromosome.predict(&inputs, DatasetId::Training, &mut column_store);
+
+            if chromosome.reorder(&mut rng) {
+                nbr_reordered += 1;
+            }
+
+            for (node_id, node) in chromosome.nodes_grid.iter().enumerate() {
+                if node.node_type != NodeType::ComputationalNode {
+                    continue;
+                }
+                let allowed = levels_back_range(node_id, params.nbr_inputs, params.nbr_rows, params.levels_back);
+                for connection in &node.connections {
+                    assert!(*connection < params.nbr_inputs || allowed.contains(connection),
+                            "node {} reads {} outside of {:?}", node_id, connection, allowed);
+                }
+            }
+            assert_eq!(chromosome.predict(&inputs, DatasetId::Training, &mut column_store), outputs);
+        }
+        assert!(nbr_reordered > 0);
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter};
+use rand_chacha::ChaCha8Rng;
+use crate::genotype::{get_active_nodes, new_nodes_grid, output_node_ids, Genotype, Phenotype};
+use crate::global_params::CgpParameters as g_params;
+use crate::standard_cgp::node::Node;
+use crate::value_domain::ValueDomain;
+
+#[derive(Clone)]
+pub struct Chromosome<D: ValueDomain> {
+    pub params: g_params,
+    pub domain: D,
+    pub nodes_grid: Vec<Node>,
+    pub output_node_ids: Vec<usize>,
+    pub active_nodes: Option<Vec<usize>>,
+    /// Fitted to the training data by the last call of `evaluate`.
+    pub output_transform: D::OutputTransform,
+    pub phenotype: Phenotype<D::Value>,
+}
+
+impl<D: ValueDomain> Display for Chromosome<D> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "+++++++++++++++++ Chromosome +++++++++++")?;
+        writeln!(f, "Nodes:")?;
+        for node in &self.nodes_grid {
+            write!(f, "{}", *node)?;
+        }
+        writeln!(f, "Active_nodes: {:?}", self.active_nodes)?;
+        writeln!(f, "Output_nodes: {:?}", self.output_node_ids)
+    }
+}
+
+impl<D: ValueDomain> Genotype<D> for Chromosome<D> {
+    type Node = Node;
+
+    fn new(params: g_params, domain: D, rng: &mut ChaCha8Rng) -> Self {
+        let nbr_functions = domain.nbr_functions();
+        let max_arity = domain.max_arity();
+        let constant_range = domain.constant_function().map(|_| params.constant_range);
+
+        let nodes_grid = new_nodes_grid(&params, |position, node_type| {
+            Node::new(position,
+                      params.nbr_inputs,
+                      params.nbr_computational_nodes,
+                      params.nbr_rows,
+                      params.levels_back,
+                      nbr_functions,
+                      max_arity,
+                      constant_range,
+                      params.constant_sigma,
+                      node_type,
+                      rng,
+            )
+        });
+        let output_node_ids = output_node_ids(&params);
+
+        Self {
+            params,
+            domain,
+            nodes_grid,
+            output_node_ids,
+            active_nodes: None,
+            output_transform: D::OutputTransform::default(),
+            phenotype: Phenotype::default(),
+        }
+    }
+
+    fn params(&self) -> &g_params {
+        &self.params
+    }
+
+    fn phenotype(&self) -> &Phenotype<D::Value> {
+        &self.phenotype
+    }
+
+    fn domain_and_phenotype_mut(&mut self) -> (&D, &mut Phenotype<D::Value>) {
+        (&self.domain, &mut self.phenotype)
+    }
+
+    fn get_active_nodes_id(&mut self) {
+        self.active_nodes = Some(get_active_nodes(&self.domain, &self.nodes_grid, &self.output_node_ids));
+    }
+
+    fn mutate_node(&mut self, node_id: usize, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        self.nodes_grid[node_id].mutate(rng);
+    }
+
+    fn mutate_gene(&mut self, node_id: usize, gene: usize, rng: &mut ChaCha8Rng) {
+        self.phenotype.tape = None;
+        self.nodes_grid[node_id].mutate_gene(gene, rng);
+    }
+
+    fn reorder(&mut self, _rng: &mut ChaCha8Rng) -> bool {
+        true
+    }
+
+    fn active_nodes(&self) -> &[usize] {
+        self.active_nodes.as_deref().unwrap_or(&[])
+    }
+
+    fn nodes_grid(&self) -> &[Node] {
+        &self.nodes_grid
+    }
+
+    fn nodes_grid_mut(&mut self) -> &mut [Node] {
+        self.phenotype.tape = None;
+        &mut self.nodes_grid
+    }
+
+    fn domain(&self) -> &D {
+        &self.domain
+    }
+
+    fn output_transform(&self) -> &D::OutputTransform {
+        &self.output_transform
+    }
+
+    fn set_output_transform(&mut self, output_transform: D::OutputTransform) {
+        self.output_transform = output_transform;
+    }
+
+    fn is_valid_connection(&self, node_id: usize, connection: usize) -> bool {
+        self.nodes_grid[node_id].is_valid_connection(connection, node_id)
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use rand_chacha::ChaCha8Rng;
+use crate::genotype::CgpNode;
+use crate::utils::node_type::NodeType;
+use crate::utils::utility_funcs::{gen_random_connection_in_range, gen_random_number_for_node, levels_back_range};
+
+
+#[derive(Clone)]
+pub struct Node {
+    pub position: usize,
+    pub node_type: NodeType,
+    pub nbr_inputs: usize,
+    pub graph_width: usize,
+    pub nbr_rows: usize,
+    pub levels_back: usize,
+    pub nbr_functions: usize,
+    pub max_arity: usize,
+    pub function_id: usize,
+    /// Input nodes have no connections, output nodes one and computational nodes `max_arity`.
+    pub connections: Vec<usize>,
+    /// Constant gene of computational nodes, output of the constant function. `None` if the
+    /// function set has no constant.
+    pub constant: Option<f32>,
+    /// Standard deviation of the Gaussian mutation of `constant`.
+    pub constant_sigma: f32,
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Node Pos: {}, ", self.position)?;
+        write!(f, "Node Type: {}, ", self.node_type)?;
+        write!(f, "Function ID: {}, ", self.function_id)?;
+        write!(f, "Connections: {:?}, ", self.connections)?;
+        writeln!(f, "Constant: {:?}, ", self.constant)
+    }
+}
+
+impl CgpNode for Node {
+    fn node_type(&self) -> &NodeType {
+        &self.node_type
+    }
+
+    fn function_id(&self) -> usize {
+        self.function_id
+    }
+
+    fn set_function_id(&mut self, function_id: usize) {
+        self.function_id = function_id;
+    }
+
+    fn connections(&self) -> &[usize] {
+        &self.connections
+    }
+
+    fn connections_mut(&mut self) -> &mut [usize] {
+        &mut self.connections
+    }
+
+    fn constant(&self) -> Option<f32> {
+        self.constant
+    }
+
+    fn set_constant(&mut self, constant: Option<f32>) {
+        self.constant = constant;
+    }
+}
+
+impl Node {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(position: usize,
+               nbr_inputs: usize,
+               graph_width: usize,
+               nbr_rows: usize,
+               levels_back: usize,
+               nbr_functions: usize,
+               max_arity: usize,
+               constant_range: Option<f32>,
+               constant_sigma: f32,
+               node_type: NodeType,
+               rng: &mut ChaCha8Rng) -> Self {
+        let function_id: usize = rng.gen_range(0..nbr_functions);
+        let connections: Vec<usize> = match node_type {
+            NodeType::InputNode => vec![],
+            NodeType::ComputationalNode => {
+                (0..max_arity)
+                    .map(|_| gen_random_connection_in_range(usize::MAX,
+                                                            nbr_inputs,
+                                                            levels_back_range(position, nbr_inputs, nbr_rows, levels_back),
+                                                            rng))
+                    .collect()
+            },
+            NodeType::OutputNode => vec![rng.gen_range(0..nbr_inputs + graph_width)],
+        };
+
+        // ephemeral random constant; only drawn if the function set has a constant
+        let constant = match (&node_type, constant_range) {
+            (NodeType::ComputationalNode, Some(constant_range)) => Some(rng.gen_range(-constant_range..=constant_range)),
+            _ => None,
+        };
+
+        Self {
+            position,
+            node_type,
+            nbr_inputs,
+            graph_width,
+            nbr_rows,
+            levels_back,
+            nbr_functions,
+            max_arity,
+            function_id,
+            connections,
+            constant,
+            constant_sigma,
+        }
+    }
+
+    pub fn mutate(&mut self, rng: &mut ChaCha8Rng) {
+        assert!(self.node_type != NodeType::InputNode);
+
+        match self.node_type {
+            NodeType::OutputNode => self.mutate_output_node(rng),
+            NodeType::ComputationalNode => {
+                // one gene per connection plus the function gene and the constant gene
+                let gene = rng.gen_range(0..self.nbr_genes());
+                self.mutate_computational_node(gene, rng)
+            }
+            _ => { panic!("Trying to mutate input node") }
+        }
+    }
+
+    /// Mutates gene `gene`; ids `0..max_arity` are the connections, `max_arity` is the function
+    /// and `max_arity + 1` the constant.
+    pub fn mutate_gene(&mut self, gene: usize, rng: &mut ChaCha8Rng) {
+        match self.node_type {
+            NodeType::OutputNode => self.mutate_output_node(rng),
+            NodeType::ComputationalNode => self.mutate_computational_node(gene, rng),
+            _ => { panic!("Trying to mutate input node") }
+        }
+    }
+
+    fn mutate_connection(connection: &mut usize, upper_range: usize, rng: &mut ChaCha8Rng) {
+        *connection = gen_random_number_for_node(*connection,
+                                                 upper_range,
+                                                 rng);
+
+    }
+
+    /// Gaussian mutation of the constant gene.
+    fn mutate_constant(&mut self, rng: &mut ChaCha8Rng) {
+        let constant = self.constant.as_mut().expect("Trying to mutate a missing constant");
+        *constant += Normal::new(0., self.constant_sigma).unwrap().sample(rng);
+    }
+
+    fn mutate_function(&mut self, rng: &mut ChaCha8Rng) {
+        self.function_id = gen_random_number_for_node(self.function_id, self.nbr_functions, rng);
+    }
+
+    fn mutate_output_node(&mut self, rng: &mut ChaCha8Rng) {
+        Node::mutate_connection(&mut self.connections[0],
+                                self.graph_width + self.nbr_inputs,
+                                rng);
+
+        assert!(self.connections[0] < self.position);
+    }
+
+    fn mutate_computational_node(&mut self, gene: usize, rng: &mut ChaCha8Rng) {
+        if gene < self.max_arity {
+            self.connections[gene] = gen_random_connection_in_range(self.connections[gene],
+                                                                        self.nbr_inputs,
+                                                                        self.connection_range(self.position),
+                                                                        rng);
+        } else if gene == self.max_arity {
+            self.mutate_function(rng);
+        } else {
+            self.mutate_constant(rng);
+        }
+
+        assert!(self.connections.iter().all(|&c| self.is_valid_connection(c, self.position)));
+    }
+
+    /// Computational nodes this node may connect to when placed at `position`.
+    fn connection_range(&self, position: usize) -> Range<usize> {
+        levels_back_range(position, self.nbr_inputs, self.nbr_rows, self.levels_back)
+    }
+
+    /// True if a computational node at `position` may read from `connection`.
+    pub fn is_valid_connection(&self, connection: usize, position: usize) -> bool {
+        connection < self.nbr_inputs || self.connection_range(position).contains(&connection)
+    }
+}
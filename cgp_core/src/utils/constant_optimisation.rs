@@ -0,0 +1,288 @@
+use crate::genotype::{CgpNode, Genotype};
+use crate::global_params::CgpParameters as g_params;
+use crate::utils::evaluation_buffer::ColumnStore;
+use crate::utils::node_type::NodeType;
+use crate::value_domain::ValueDomain;
+
+/// Reflection, expansion, contraction and shrink coefficients of the Nelder-Mead method.
+const REFLECTION: f32 = 1.;
+const EXPANSION: f32 = 2.;
+const CONTRACTION: f32 = 0.5;
+const SHRINK: f32 = 0.5;
+/// The search stops when the fitness values of the simplex differ by less than this.
+const TOLERANCE: f32 = 1e-7;
+
+/// Tunes the constants of the active constant nodes of `chromosome` with the gradient-free
+/// Nelder-Mead simplex method. `fitness` is the current fitness of `chromosome`, so the start
+/// point is not evaluated again. At most `max_evaluations` evaluations on `data` are used, the
+/// best constants found are written back into the genotype. The columns of the evaluations
+/// are computed into `column_store`.
+///
+/// Returns the fitness of the tuned chromosome and the number of evaluations used.
+pub fn optimise_constants<D: ValueDomain, G: Genotype<D>>(chromosome: &mut G,
+                                                          data: &[Vec<D::Value>],
+                                                          label: &[Vec<D::Value>],
+                                                          fitness: f32,
+                                                          max_evaluations: usize,
+                                                          column_store: &mut ColumnStore<D::Value>) -> (f32, usize) {
+    let constant_function = match chromosome.domain().constant_function() {
+        Some(constant_function) => constant_function,
+        None => return (fitness, 0),
+    };
+
+    let constant_node_ids: Vec<usize> = chromosome.active_nodes()
+        .iter()
+        .copied()
+        .filter(|node_id| {
+            let node = &chromosome.nodes_grid()[*node_id];
+            *node.node_type() == NodeType::ComputationalNode && node.function_id() == constant_function
+        })
+        .collect();
+    if constant_node_ids.is_empty() {
+        return (fitness, 0);
+    }
+
+    let start: Vec<f32> = constant_node_ids.iter()
+        .map(|node_id| chromosome.nodes_grid()[*node_id].constant().unwrap())
+        .collect();
+
+    let mut objective = ConstantObjective {
+        best_point: start.clone(),
+        best_fitness: fitness,
+        best_output_transform: chromosome.output_transform().clone(),
+        chromosome,
+        constant_node_ids,
+        data,
+        label,
+        column_store,
+        nbr_evaluations: 0,
+        max_evaluations,
+    };
+
+    nelder_mead(&mut objective, start, fitness);
+
+    // the last evaluated point is not necessarily the best one
+    let best_point = objective.best_point.clone();
+    objective.set_constants(&best_point);
+    objective.chromosome.set_output_transform(objective.best_output_transform);
+    (objective.best_fitness, objective.nbr_evaluations)
+}
+
+/// Evaluations one tuning may use: `constant_optimisation_evaluations`, but not more than are
+/// left of `max_evaluations` after `nbr_evaluations`.
+pub fn get_tuning_budget(params: &g_params, nbr_evaluations: usize) -> usize {
+    match params.max_evaluations {
+        0 => params.constant_optimisation_evaluations,
+        max_evaluations => params.constant_optimisation_evaluations.min(max_evaluations.saturating_sub(nbr_evaluations)),
+    }
+}
+
+/// Fitness of a chromosome as function of the constants of `constant_node_ids`.
+/// Keeps track of the best point evaluated so far.
+struct ConstantObjective<'a, D: ValueDomain, G: Genotype<D>> {
+    best_point: Vec<f32>,
+    best_fitness: f32,
+    best_output_transform: D::OutputTransform,
+    chromosome: &'a mut G,
+    constant_node_ids: Vec<usize>,
+    data: &'a [Vec<D::Value>],
+    label: &'a [Vec<D::Value>],
+    column_store: &'a mut ColumnStore<D::Value>,
+    nbr_evaluations: usize,
+    max_evaluations: usize,
+}
+
+impl<D: ValueDomain, G: Genotype<D>> ConstantObjective<'_, D, G> {
+    fn set_constants(&mut self, point: &[f32]) {
+        for (node_id, constant) in self.constant_node_ids.iter().zip(point.iter()) {
+            self.chromosome.nodes_grid_mut()[*node_id].set_constant(Some(*constant));
+        }
+    }
+
+    /// Fitness at `point`; non-finite fitness values are mapped to `f32::MAX`.
+    /// `None` if the evaluation budget is used up.
+    fn evaluate(&mut self, point: &[f32]) -> Option<f32> {
+        if self.nbr_evaluations >= self.max_evaluations {
+            return None;
+        }
+        self.nbr_evaluations += 1;
+
+        self.set_constants(point);
+        let mut fitness = self.chromosome.evaluate(self.data, self.label, self.column_store);
+        if !fitness.is_finite() {
+            fitness = f32::MAX;
+        }
+
+        if fitness < self.best_fitness {
+            self.best_point = point.to_vec();
+            self.best_fitness = fitness;
+            self.best_output_transform = self.chromosome.output_transform().clone();
+        }
+        Some(fitness)
+    }
+}
+
+/// Minimises `objective` starting at `start` with fitness `start_fitness` until the simplex
+/// converged or the evaluation budget is used up. The best point is tracked by `objective`.
+fn nelder_mead<D: ValueDomain, G: Genotype<D>>(objective: &mut ConstantObjective<D, G>,
+                                               start: Vec<f32>,
+                                               start_fitness: f32) {
+    let dimension = start.len();
+
+    let mut simplex: Vec<(Vec<f32>, f32)> = Vec::with_capacity(dimension + 1);
+    simplex.push((start.clone(), start_fitness));
+    for i in 0..dimension {
+        let mut point = start.clone();
+        point[i] += (0.1 * point[i].abs()).max(0.1);
+
+        match objective.evaluate(&point) {
+            Some(fitness) => simplex.push((point, fitness)),
+            None => return,
+        }
+    }
+
+    loop {
+        simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        if simplex[dimension].1 - simplex[0].1 < TOLERANCE {
+            break;
+        }
+
+        let worst = simplex[dimension].clone();
+        let second_worst_fitness = simplex[dimension - 1].1;
+
+        // centroid of all points except the worst
+        let centroid: Vec<f32> = (0..dimension)
+            .map(|i| simplex[..dimension].iter().map(|(point, _)| point[i]).sum::<f32>() / dimension as f32)
+            .collect();
+        let towards = |target: &[f32], factor: f32| -> Vec<f32> {
+            centroid.iter().zip(target.iter()).map(|(c, t)| c + factor * (t - c)).collect()
+        };
+
+        let reflected = towards(&worst.0, -REFLECTION);
+        let reflected_fitness = match objective.evaluate(&reflected) {
+            Some(fitness) => fitness,
+            None => break,
+        };
+
+        if reflected_fitness < simplex[0].1 {
+            let expanded = towards(&reflected, EXPANSION);
+            let expanded_fitness = match objective.evaluate(&expanded) {
+                Some(fitness) => fitness,
+                None => break,
+            };
+
+            simplex[dimension] = if expanded_fitness < reflected_fitness {
+                (expanded, expanded_fitness)
+            } else {
+                (reflected, reflected_fitness)
+            };
+            continue;
+        }
+
+        if reflected_fitness < second_worst_fitness {
+            simplex[dimension] = (reflected, reflected_fitness);
+            continue;
+        }
+
+        // contract outside if the reflected point is better than the worst, else inside
+        let contracted = if reflected_fitness < worst.1 {
+            towards(&reflected, CONTRACTION)
+        } else {
+            towards(&worst.0, CONTRACTION)
+        };
+        let contracted_fitness = match objective.evaluate(&contracted) {
+            Some(fitness) => fitness,
+            None => break,
+        };
+
+        if contracted_fitness < reflected_fitness.min(worst.1) {
+            simplex[dimension] = (contracted, contracted_fitness);
+            continue;
+        }
+
+        // shrink all points towards the best one
+        let best = simplex[0].0.clone();
+        for vertex in simplex.iter_mut().skip(1) {
+            let point: Vec<f32> = best.iter()
+                .zip(vertex.0.iter())
+                .map(|(b, p)| b + SHRINK * (p - b))
+                .collect();
+
+            match objective.evaluate(&point) {
+                Some(fitness) => *vertex = (point, fitness),
+                None => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::SeedableRng;
+    use crate::standard_cgp::chromosome::Chromosome;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain, ADD, CONSTANT};
+    use super::*;
+
+    /// `x0 + c` with the constant `c` and labels `x0 + 0.7`, so the optimal constant is 0.7.
+    fn get_shifted_input(constant: f32) -> (Chromosome<TestDomain>, Vec<Vec<f32>>, Vec<Vec<f32>>) {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut chromosome = Chromosome::new(test_params(1, 1, 2, 1, 2), TestDomain { with_constant: true }, &mut rng);
+
+        let nodes_grid = chromosome.nodes_grid_mut();
+        nodes_grid[1].set_function_id(CONSTANT);
+        nodes_grid[1].set_constant(Some(constant));
+        nodes_grid[2].set_function_id(ADD);
+        nodes_grid[2].connections_mut()[..2].copy_from_slice(&[0, 1]);
+        nodes_grid[3].connections_mut()[0] = 2;
+        chromosome.get_active_nodes_id();
+
+        let data = test_inputs(1, 10);
+        let label = vec![data[0].iter().map(|x| x + 0.7).collect()];
+        (chromosome, data, label)
+    }
+
+    #[test]
+    fn optimise_constants_finds_the_optimal_constant() {
+        let (mut chromosome, data, label) = get_shifted_input(-2.);
+        let mut column_store = ColumnStore::default();
+        let fitness = chromosome.evaluate(&data, &label, &mut column_store);
+
+        let (tuned_fitness, nbr_evaluations) = optimise_constants(&mut chromosome, &data, &label, fitness, 100, &mut column_store);
+
+        assert!(tuned_fitness < 1e-3, "fitness {} after tuning", tuned_fitness);
+        assert!(nbr_evaluations <= 100);
+        assert!((chromosome.nodes_grid()[1].constant().unwrap() - 0.7).abs() < 1e-3);
+        assert_eq!(chromosome.evaluate(&data, &label, &mut column_store), tuned_fitness);
+    }
+
+    #[test]
+    fn optimise_constants_never_writes_back_a_worse_point() {
+        for max_evaluations in 0..20 {
+            // the first step of the simplex increases the constant, away from the optimum
+            let (mut chromosome, data, label) = get_shifted_input(1.);
+            let mut column_store = ColumnStore::default();
+            let fitness = chromosome.evaluate(&data, &label, &mut column_store);
+
+            let (tuned_fitness, nbr_evaluations) = optimise_constants(&mut chromosome, &data, &label, fitness, max_evaluations, &mut column_store);
+
+            assert!(tuned_fitness <= fitness);
+            assert!(nbr_evaluations <= max_evaluations);
+            assert_eq!(chromosome.evaluate(&data, &label, &mut column_store), tuned_fitness);
+            if max_evaluations <= 1 {
+                assert_eq!(chromosome.nodes_grid()[1].constant(), Some(1.));
+            }
+        }
+    }
+
+    #[test]
+    fn tuning_budget_is_clamped_to_the_evaluations_left() {
+        let mut params = test_params(1, 1, 2, 1, 2);
+        params.constant_optimisation_evaluations = 100;
+        assert_eq!(get_tuning_budget(&params, 1_000), 100);
+
+        params.max_evaluations = 1_050;
+        assert_eq!(get_tuning_budget(&params, 1_000), 50);
+        assert_eq!(get_tuning_budget(&params, 1_100), 0);
+    }
+}
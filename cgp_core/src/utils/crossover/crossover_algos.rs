@@ -0,0 +1,660 @@
+#![allow(clippy::too_many_arguments)]
+
+use rand::Rng;
+use rand::distributions::{Distribution, Uniform};
+use rand::prelude::IteratorRandom;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use std::cmp::min;
+use crate::genotype::{CgpNode, Genotype};
+use crate::global_params::CgpParameters as g_params;
+use crate::utils::crossover::semantic_operators;
+use crate::utils::node_type::NodeType;
+use crate::value_domain::ValueDomain;
+
+/// Creates the children `child1_id` and `child2_id` of `new_population` from the parents
+/// `parent1_id` and `parent2_id` of `population` with the operator `crossover_type`, see
+/// `--crossover-type`. Returns false if semantic crossover fell back to uniform crossover.
+pub fn crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                 crossover_type: usize,
+                                                 rng: &mut ChaCha8Rng,
+                                                 population: &[G],
+                                                 new_population: &mut [G],
+                                                 child1_id: usize,
+                                                 child2_id: usize,
+                                                 parent1_id: usize,
+                                                 parent2_id: usize) -> bool {
+    let operator = match crossover_type {
+        0 => single_point_crossover,
+        1 => multi_point_crossover,
+        2 => uniform_crossover,
+        3 => no_crossover,
+        4 => subgraph_crossover,
+        5 => discrete_crossover,
+        6 => block_crossover,
+        7 => output_swap_crossover,
+        8 => homologous_crossover,
+        9 => return semantic_operators::semantic_crossover(params, rng, population, new_population,
+                                                           child1_id, child2_id, parent1_id, parent2_id),
+        _ => panic!("Wrong crossover type: {}", crossover_type),
+    };
+    operator(params, rng, population, new_population, child1_id, child2_id, parent1_id, parent2_id);
+    true
+}
+
+
+pub fn single_point_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                              rng: &mut ChaCha8Rng,
+                                                              population: &[G],
+                                                              new_population: &mut [G],
+                                                              child1_id: usize,
+                                                              child2_id: usize,
+                                                              parent1_id: usize,
+                                                              parent2_id: usize) {
+    // Generate range between computational nodes
+    let crossover_point = rng.gen_range(params.nbr_inputs..get_crossover_end(params));
+
+    let mut cross_chromo_1: G = population[parent1_id].clone();
+    let mut cross_chromo_2: G = population[parent2_id].clone();
+
+    cross_chromo_1
+        .nodes_grid_mut()[..crossover_point]
+        .swap_with_slice(&mut cross_chromo_2.nodes_grid_mut()[..crossover_point]);
+
+    cross_chromo_1.repair(rng);
+    cross_chromo_2.repair(rng);
+
+    new_population[child1_id] = cross_chromo_1;
+    new_population[child2_id] = cross_chromo_2;
+}
+
+
+pub fn multi_point_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                             rng: &mut ChaCha8Rng,
+                                                             population: &[G],
+                                                             new_population: &mut [G],
+                                                             child1_id: usize,
+                                                             child2_id: usize,
+                                                             parent1_id: usize,
+                                                             parent2_id: usize) {
+    // Generate range between computational nodes
+    let mut cross_chromo_1: G = population[parent1_id].clone();
+    let mut cross_chromo_2: G = population[parent2_id].clone();
+
+    let crossover_end = get_crossover_end(params);
+    let crossover_points: Vec<usize> = (params.nbr_inputs..crossover_end)
+        .choose_multiple(rng, params.multi_point_n);
+
+    for point in crossover_points {
+        cross_chromo_1.nodes_grid_mut()[point..crossover_end]
+            .swap_with_slice(&mut cross_chromo_2.nodes_grid_mut()[point..crossover_end]);
+    }
+
+    cross_chromo_1.repair(rng);
+    cross_chromo_2.repair(rng);
+
+    new_population[child1_id] = cross_chromo_1;
+    new_population[child2_id] = cross_chromo_2;
+}
+
+
+pub fn uniform_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                         rng: &mut ChaCha8Rng,
+                                                         population: &[G],
+                                                         new_population: &mut [G],
+                                                         child1_id: usize,
+                                                         child2_id: usize,
+                                                         parent1_id: usize,
+                                                         parent2_id: usize) {
+    let between = Uniform::from(0..=1);
+    let mut cross_chromo_1: G = population[parent1_id].clone();
+    let mut cross_chromo_2: G = population[parent2_id].clone();
+
+    for node_id in params.nbr_inputs..get_crossover_end(params) {
+        let cross = between.sample(rng);
+
+        if cross == 0 {
+            std::mem::swap(&mut cross_chromo_1.nodes_grid_mut()[node_id], &mut cross_chromo_2.nodes_grid_mut()[node_id])
+        }
+    }
+
+    cross_chromo_1.repair(rng);
+    cross_chromo_2.repair(rng);
+
+    new_population[child1_id] = cross_chromo_1;
+    new_population[child2_id] = cross_chromo_2;
+}
+
+/// Discrete recombination: each gene of a computational node (function, connections and
+/// constant) is swapped independently with probability 0.5.
+pub fn discrete_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                          rng: &mut ChaCha8Rng,
+                                                          population: &[G],
+                                                          new_population: &mut [G],
+                                                          child1_id: usize,
+                                                          child2_id: usize,
+                                                          parent1_id: usize,
+                                                          parent2_id: usize) {
+    let between = Uniform::from(0..=1);
+    let mut cross_chromo_1: G = population[parent1_id].clone();
+    let mut cross_chromo_2: G = population[parent2_id].clone();
+
+    for node_id in params.nbr_inputs..params.nbr_inputs + params.nbr_computational_nodes {
+        let node_1 = &mut cross_chromo_1.nodes_grid_mut()[node_id];
+        let node_2 = &mut cross_chromo_2.nodes_grid_mut()[node_id];
+
+        if between.sample(rng) == 0 {
+            let function_id = node_1.function_id();
+            node_1.set_function_id(node_2.function_id());
+            node_2.set_function_id(function_id);
+        }
+
+        if node_1.constant().is_some() && between.sample(rng) == 0 {
+            let constant = node_1.constant();
+            node_1.set_constant(node_2.constant());
+            node_2.set_constant(constant);
+        }
+
+        for (connection_1, connection_2) in node_1.connections_mut()
+            .iter_mut()
+            .zip(node_2.connections_mut().iter_mut()) {
+            if between.sample(rng) == 0 {
+                std::mem::swap(connection_1, connection_2);
+            }
+        }
+    }
+
+    cross_chromo_1.repair(rng);
+    cross_chromo_2.repair(rng);
+
+    new_population[child1_id] = cross_chromo_1;
+    new_population[child2_id] = cross_chromo_2;
+}
+
+/// Swaps a contiguous block of active nodes. The block bounds are drawn from the active
+/// computational nodes of both parents; inside the block, only nodes that are active in at
+/// least one parent are swapped.
+pub fn block_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                       rng: &mut ChaCha8Rng,
+                                                       population: &[G],
+                                                       new_population: &mut [G],
+                                                       child1_id: usize,
+                                                       child2_id: usize,
+                                                       parent1_id: usize,
+                                                       parent2_id: usize) {
+    let mut active_comp_nodes = get_active_computational_nodes(params, &population[parent1_id]);
+    active_comp_nodes.extend(get_active_computational_nodes(params, &population[parent2_id]));
+    active_comp_nodes.sort_unstable();
+    active_comp_nodes.dedup();
+
+    let mut cross_chromo_1: G = population[parent1_id].clone();
+    let mut cross_chromo_2: G = population[parent2_id].clone();
+
+    if !active_comp_nodes.is_empty() {
+        let block_start = rng.gen_range(0..active_comp_nodes.len());
+        let block_end = rng.gen_range(block_start..active_comp_nodes.len());
+
+        for node_id in &active_comp_nodes[block_start..=block_end] {
+            std::mem::swap(&mut cross_chromo_1.nodes_grid_mut()[*node_id], &mut cross_chromo_2.nodes_grid_mut()[*node_id])
+        }
+    }
+
+    cross_chromo_1.repair(rng);
+    cross_chromo_2.repair(rng);
+
+    new_population[child1_id] = cross_chromo_1;
+    new_population[child2_id] = cross_chromo_2;
+}
+
+/// Output swap crossover: every output is chosen with probability 0.5. The first child is a
+/// copy of the first parent that receives the output cones (the output node and all nodes it
+/// depends on) of the chosen outputs from the second parent, and vice versa.
+/// A cone is only received if it does not overwrite a node the kept outputs depend on.
+pub fn output_swap_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                             rng: &mut ChaCha8Rng,
+                                                             population: &[G],
+                                                             new_population: &mut [G],
+                                                             child1_id: usize,
+                                                             child2_id: usize,
+                                                             parent1_id: usize,
+                                                             parent2_id: usize) {
+    let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+    let swapped_outputs: Vec<bool> = (0..params.nbr_outputs)
+        .map(|_| rng.gen::<bool>())
+        .collect();
+
+    for (child_id, base_id, donor_id) in [(child1_id, parent1_id, parent2_id), (child2_id, parent2_id, parent1_id)] {
+        let mut cross_chromo: G = population[base_id].clone();
+
+        // nodes the kept outputs depend on must not be overwritten
+        let mut protected: Vec<usize> = (0..params.nbr_outputs)
+            .filter(|output| !swapped_outputs[*output])
+            .flat_map(|output| get_output_cone(&population[base_id], first_output_id + output))
+            .collect();
+
+        for output in (0..params.nbr_outputs).filter(|output| swapped_outputs[*output]) {
+            let cone = get_output_cone(&population[donor_id], first_output_id + output);
+            if cone.iter().any(|node_id| protected.contains(node_id)) {
+                continue;
+            }
+
+            for node_id in &cone {
+                cross_chromo.nodes_grid_mut()[*node_id] = population[donor_id].nodes_grid()[*node_id].clone();
+            }
+            protected.extend(cone);
+        }
+
+        cross_chromo.repair(rng);
+        new_population[child_id] = cross_chromo;
+    }
+}
+
+/// The output node `output_node_id` and all computational nodes it depends on.
+fn get_output_cone<D: ValueDomain, G: Genotype<D>>(chromosome: &G, output_node_id: usize) -> Vec<usize> {
+    let nodes_grid = chromosome.nodes_grid();
+
+    let mut cone: Vec<usize> = vec![output_node_id];
+    let mut to_visit: Vec<usize> = vec![output_node_id];
+
+    while let Some(node_id) = to_visit.pop() {
+        let node = &nodes_grid[node_id];
+        let nbr_read = match node.node_type() {
+            NodeType::InputNode => continue,
+            NodeType::ComputationalNode => chromosome.domain().arity(node.function_id()),
+            NodeType::OutputNode => 1,
+        };
+
+        for connection in &node.connections()[..nbr_read] {
+            if *nodes_grid[*connection].node_type() == NodeType::ComputationalNode && !cone.contains(connection) {
+                cone.push(*connection);
+                to_visit.push(*connection);
+            }
+        }
+    }
+
+    cone
+}
+
+/// Homologous crossover: parents are aligned by the rank of their active computational nodes
+/// in evaluation order instead of their position in the grid. Every aligned pair of active
+/// nodes is swapped with probability 0.5. Inactive nodes stay with their parent.
+pub fn homologous_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                            rng: &mut ChaCha8Rng,
+                                                            population: &[G],
+                                                            new_population: &mut [G],
+                                                            child1_id: usize,
+                                                            child2_id: usize,
+                                                            parent1_id: usize,
+                                                            parent2_id: usize) {
+    let comp_nodes = params.nbr_inputs..params.nbr_inputs + params.nbr_computational_nodes;
+    // active nodes are already sorted in evaluation order
+    let active_comp_nodes_p1: Vec<usize> = population[parent1_id].active_nodes()
+        .iter()
+        .copied()
+        .filter(|node_id| comp_nodes.contains(node_id))
+        .collect();
+    let active_comp_nodes_p2: Vec<usize> = population[parent2_id].active_nodes()
+        .iter()
+        .copied()
+        .filter(|node_id| comp_nodes.contains(node_id))
+        .collect();
+
+    let mut cross_chromo_1: G = population[parent1_id].clone();
+    let mut cross_chromo_2: G = population[parent2_id].clone();
+
+    for rank in 0..min(active_comp_nodes_p1.len(), active_comp_nodes_p2.len()) {
+        if rng.gen::<bool>() {
+            cross_chromo_1.nodes_grid_mut()[active_comp_nodes_p1[rank]] = get_aligned_node(params,
+                                                                                             &population[parent2_id],
+                                                                                             &active_comp_nodes_p2,
+                                                                                             &population[parent1_id],
+                                                                                             &active_comp_nodes_p1,
+                                                                                             rank);
+            cross_chromo_2.nodes_grid_mut()[active_comp_nodes_p2[rank]] = get_aligned_node(params,
+                                                                                             &population[parent1_id],
+                                                                                             &active_comp_nodes_p1,
+                                                                                             &population[parent2_id],
+                                                                                             &active_comp_nodes_p2,
+                                                                                             rank);
+        }
+    }
+
+    cross_chromo_1.repair(rng);
+    cross_chromo_2.repair(rng);
+
+    new_population[child1_id] = cross_chromo_1;
+    new_population[child2_id] = cross_chromo_2;
+}
+
+/// The active node of rank `rank` of `donor`, placed at the position of the node of the same
+/// rank in `base`. Connections to active nodes are remapped by rank. Connections that cannot
+/// be remapped or would be invalid at the new position keep the gene of the base node.
+fn get_aligned_node<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                    donor: &G,
+                                                    donor_active: &[usize],
+                                                    base: &G,
+                                                    base_active: &[usize],
+                                                    rank: usize) -> G::Node {
+    let base_node_id = base_active[rank];
+    let donor_node = &donor.nodes_grid()[donor_active[rank]];
+
+    // the base node keeps all position dependent fields
+    let mut node = base.nodes_grid()[base_node_id].clone();
+    node.set_function_id(donor_node.function_id());
+    node.set_constant(donor_node.constant());
+
+    for (index, connection) in donor_node.connections().iter().enumerate() {
+        let remapped_connection = if *connection < params.nbr_inputs {
+            Some(*connection)
+        } else {
+            donor_active.iter()
+                .position(|node_id| node_id == connection)
+                .and_then(|connection_rank| base_active.get(connection_rank).copied())
+        };
+
+        if let Some(remapped_connection) = remapped_connection {
+            if base.is_valid_connection(base_node_id, remapped_connection) {
+                node.connections_mut()[index] = remapped_connection;
+            }
+        }
+    }
+
+    node
+}
+
+/// End of the node range exchanged by point, multi-point and uniform crossover.
+fn get_crossover_end(params: &g_params) -> usize {
+    if params.crossover_include_outputs {
+        params.nbr_inputs + params.nbr_computational_nodes + params.nbr_outputs
+    } else {
+        params.nbr_inputs + params.nbr_computational_nodes
+    }
+}
+
+pub fn no_crossover<D: ValueDomain, G: Genotype<D>>(_params: &g_params,
+                                                    _rng: &mut ChaCha8Rng,
+                                                    population: &[G],
+                                                    new_population: &mut [G],
+                                                    child1_id: usize,
+                                                    child2_id: usize,
+                                                    parent1_id: usize,
+                                                    parent2_id: usize) {
+
+    new_population[child1_id] = population[parent1_id].clone();
+    new_population[child2_id] = population[parent2_id].clone();
+}
+
+/// Subgraph crossover (Kalkreuth et al., 2017).
+///
+/// Each parent proposes a crossover point among its active computational nodes, the smaller
+/// one is used. The child takes the nodes in front of the point from the parent that
+/// proposed it and all other nodes from the second parent. Both sections are connected
+/// afterwards, see `connect_subgraphs`.
+pub fn subgraph_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                          rng: &mut ChaCha8Rng,
+                                                          population: &[G],
+                                                          new_population: &mut [G],
+                                                          child1_id: usize,
+                                                          child2_id: usize,
+                                                          parent1_id: usize,
+                                                          parent2_id: usize) {
+    // ## Preliminary
+    // get the active computational nodes, sorted by position
+    let active_comp_nodes_p1 = get_active_computational_nodes(params, &population[parent1_id]);
+    let active_comp_nodes_p2 = get_active_computational_nodes(params, &population[parent2_id]);
+
+    if active_comp_nodes_p1.is_empty() || active_comp_nodes_p2.is_empty() {
+        new_population[child1_id] = population[parent1_id].clone();
+        new_population[child2_id] = population[parent2_id].clone();
+        return;
+    }
+
+    for child_id in [child1_id, child2_id] {
+        // ## Step 1: Define a general crossover point
+        let cp1 = *active_comp_nodes_p1.choose(rng).unwrap();
+        let cp2 = *active_comp_nodes_p2.choose(rng).unwrap();
+        let cp = min(cp1, cp2);
+
+        // ## Step 2: Copy genetic material in front of the crossover point from the parent
+        // that proposed it, everything behind from the other parent
+        let (front_parent_id, back_parent_id, front_active) = if cp1 <= cp2 {
+            (parent1_id, parent2_id, &active_comp_nodes_p1)
+        } else {
+            (parent2_id, parent1_id, &active_comp_nodes_p2)
+        };
+
+        let mut cross_chromo: G = population[front_parent_id].clone();
+        cross_chromo.nodes_grid_mut()[cp..]
+            .clone_from_slice(&population[back_parent_id].nodes_grid()[cp..]);
+
+        // ## Step 3: Connect both sections
+        let front_active: Vec<usize> = front_active.iter()
+            .copied()
+            .filter(|node_id| *node_id < cp)
+            .collect();
+        let mut back_active: Vec<usize> = population[back_parent_id].active_nodes()
+            .iter()
+            .copied()
+            .filter(|node_id| *node_id >= cp)
+            .collect();
+        back_active.sort_unstable();
+
+        connect_subgraphs(params, rng, &mut cross_chromo, cp, &front_active, &back_active);
+
+        cross_chromo.repair(rng);
+        new_population[child_id] = cross_chromo;
+    }
+}
+
+/// Active computational nodes of `chromosome`, sorted by position.
+fn get_active_computational_nodes<D: ValueDomain, G: Genotype<D>>(params: &g_params, chromosome: &G) -> Vec<usize> {
+    let mut active_comp_nodes: Vec<usize> = chromosome.active_nodes()
+        .iter()
+        .copied()
+        .filter(|node_id| (params.nbr_inputs..params.nbr_inputs + params.nbr_computational_nodes).contains(node_id))
+        .collect();
+    active_comp_nodes.sort_unstable();
+
+    active_comp_nodes
+}
+
+/// Connects the section in front of the crossover point `cp` with the section behind it.
+///
+/// Neighbourhood connect: the first active node behind `cp` reads from the last active node
+/// in front of it.
+/// Random active connect: every other connection of an active node behind `cp` that reads
+/// from an inactive node in front of `cp` is redirected to a random active node in front of
+/// `cp` or to an input. Candidates violating levels-back are skipped.
+fn connect_subgraphs<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                     rng: &mut ChaCha8Rng,
+                                                     chromosome: &mut G,
+                                                     cp: usize,
+                                                     front_active: &[usize],
+                                                     back_active: &[usize]) {
+    let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+
+    // number of connections each node behind cp actually reads
+    let nbr_read_connections: Vec<usize> = back_active.iter()
+        .map(|node_id| {
+            let node = &chromosome.nodes_grid()[*node_id];
+            match node.node_type() {
+                NodeType::ComputationalNode => chromosome.domain().arity(node.function_id()),
+                _ => 1,
+            }
+        })
+        .collect();
+
+    let candidates: Vec<usize> = (0..params.nbr_inputs)
+        .chain(front_active.iter().copied())
+        .collect();
+
+    // candidates each node behind cp may connect to; output nodes may connect to any node
+    let valid_candidates: Vec<Vec<usize>> = back_active.iter()
+        .map(|node_id| candidates.iter()
+            .copied()
+            .filter(|candidate| *node_id >= first_output_id || chromosome.is_valid_connection(*node_id, *candidate))
+            .collect())
+        .collect();
+
+    let nodes_grid = chromosome.nodes_grid_mut();
+
+    // Neighbourhood connect
+    if let (Some(last_front), Some(first_back)) = (front_active.last(), back_active.first()) {
+        let connection = &mut nodes_grid[*first_back].connections_mut()[0];
+        if *connection < cp && valid_candidates[0].contains(last_front) {
+            *connection = *last_front;
+        }
+    }
+
+    // Random active connect
+    for ((node_id, nbr_read), valid_candidates) in back_active.iter().zip(nbr_read_connections).zip(valid_candidates) {
+        for connection in nodes_grid[*node_id].connections_mut()[..nbr_read].iter_mut() {
+            if *connection < cp && !candidates.contains(connection) {
+                if let Some(new_connection) = valid_candidates.choose(rng) {
+                    *connection = *new_connection;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::algo::is_cyclic_directed;
+    use petgraph::graph::DiGraph;
+    use rand_core::SeedableRng;
+    use crate::dag::chromosome_dag::Chromosome as DagChromosome;
+    use crate::reorder::chromosome_reorder_equidistant::Chromosome as ReorderChromosome;
+    use crate::standard_cgp::chromosome::Chromosome as StandardChromosome;
+    use crate::utils::test_domain::{test_params, TestDomain};
+    use super::*;
+
+    type Operator<G> = fn(&g_params, &mut ChaCha8Rng, &[G], &mut [G], usize, usize, usize, usize);
+
+    /// A grid with 2 rows and a levels-back of 3 columns, so connections are restricted.
+    fn crossover_params() -> g_params {
+        let mut params = test_params(2, 2, 30, 2, 3);
+        params.multi_point_n = 3;
+        params
+    }
+
+    /// Two random parents, whose active nodes are known, and their children by `operator`.
+    fn get_offspring<G: Genotype<TestDomain>>(params: &g_params, operator: Operator<G>, rng: &mut ChaCha8Rng) -> (Vec<G>, Vec<G>) {
+        let parents: Vec<G> = (0..2)
+            .map(|_| {
+                let mut parent = G::new(params.clone(), TestDomain { with_constant: true }, rng);
+                parent.get_active_nodes_id();
+                parent
+            })
+            .collect();
+
+        let mut children = parents.clone();
+        operator(params, rng, &parents, &mut children, 0, 1, 0, 1);
+        (parents, children)
+    }
+
+    fn genes<G: Genotype<TestDomain>>(chromosome: &G) -> Vec<(usize, Vec<usize>, Option<f32>)> {
+        chromosome.nodes_grid()
+            .iter()
+            .map(|node| (node.function_id(), node.connections().to_vec(), node.constant()))
+            .collect()
+    }
+
+    /// Every connection is valid in the variant, the graph of all connections has no cycle
+    /// and `repair` does not change the genes.
+    fn assert_valid<G: Genotype<TestDomain>>(params: &g_params, child: &G, rng: &mut ChaCha8Rng) {
+        let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+        let mut graph: DiGraph<(), ()> = DiGraph::new();
+        let nodes: Vec<_> = child.nodes_grid().iter().map(|_| graph.add_node(())).collect();
+
+        for (node_id, node) in child.nodes_grid().iter().enumerate() {
+            for connection in node.connections() {
+                match node.node_type() {
+                    NodeType::ComputationalNode => assert!(child.is_valid_connection(node_id, *connection),
+                                                           "node {} reads {}", node_id, connection),
+                    _ => assert!(*connection < first_output_id, "output {} reads {}", node_id, connection),
+                }
+                graph.add_edge(nodes[*connection], nodes[node_id], ());
+            }
+        }
+        assert!(!is_cyclic_directed(&graph));
+
+        let mut repaired = child.clone();
+        repaired.repair(rng);
+        assert!(genes(&repaired) == genes(child), "repair changed the offspring");
+    }
+
+    fn assert_valid_offspring<G: Genotype<TestDomain>>(operator: Operator<G>) {
+        let params = crossover_params();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        for _ in 0..50 {
+            let (_, children) = get_offspring(&params, operator, &mut rng);
+            for child in &children {
+                assert_valid(&params, child, &mut rng);
+            }
+        }
+    }
+
+    #[test]
+    fn subgraph_crossover_creates_valid_offspring() {
+        assert_valid_offspring::<StandardChromosome<TestDomain>>(subgraph_crossover);
+        assert_valid_offspring::<ReorderChromosome<TestDomain>>(subgraph_crossover);
+        assert_valid_offspring::<DagChromosome<TestDomain>>(subgraph_crossover);
+    }
+
+    #[test]
+    fn discrete_crossover_creates_valid_offspring() {
+        assert_valid_offspring::<StandardChromosome<TestDomain>>(discrete_crossover);
+        assert_valid_offspring::<ReorderChromosome<TestDomain>>(discrete_crossover);
+        assert_valid_offspring::<DagChromosome<TestDomain>>(discrete_crossover);
+    }
+
+    #[test]
+    fn block_crossover_creates_valid_offspring() {
+        assert_valid_offspring::<StandardChromosome<TestDomain>>(block_crossover);
+        assert_valid_offspring::<ReorderChromosome<TestDomain>>(block_crossover);
+        assert_valid_offspring::<DagChromosome<TestDomain>>(block_crossover);
+    }
+
+    #[test]
+    fn output_swap_crossover_creates_valid_offspring() {
+        assert_valid_offspring::<StandardChromosome<TestDomain>>(output_swap_crossover);
+        assert_valid_offspring::<ReorderChromosome<TestDomain>>(output_swap_crossover);
+        assert_valid_offspring::<DagChromosome<TestDomain>>(output_swap_crossover);
+    }
+
+    fn assert_outputs_untouched<G: Genotype<TestDomain>>(operator: Operator<G>) {
+        let mut params = crossover_params();
+        params.crossover_include_outputs = false;
+        let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        for _ in 0..50 {
+            let (parents, children) = get_offspring(&params, operator, &mut rng);
+            for (parent, child) in parents.iter().zip(children.iter()) {
+                assert!(genes(parent)[first_output_id..] == genes(child)[first_output_id..]);
+            }
+        }
+    }
+
+    #[test]
+    fn point_and_uniform_crossover_keep_outputs_if_excluded() {
+        assert_outputs_untouched::<StandardChromosome<TestDomain>>(single_point_crossover);
+        assert_outputs_untouched::<StandardChromosome<TestDomain>>(multi_point_crossover);
+        assert_outputs_untouched::<StandardChromosome<TestDomain>>(uniform_crossover);
+        assert_outputs_untouched::<ReorderChromosome<TestDomain>>(single_point_crossover);
+        assert_outputs_untouched::<ReorderChromosome<TestDomain>>(multi_point_crossover);
+        assert_outputs_untouched::<ReorderChromosome<TestDomain>>(uniform_crossover);
+        assert_outputs_untouched::<DagChromosome<TestDomain>>(single_point_crossover);
+        assert_outputs_untouched::<DagChromosome<TestDomain>>(multi_point_crossover);
+        assert_outputs_untouched::<DagChromosome<TestDomain>>(uniform_crossover);
+    }
+
+    #[test]
+    fn homologous_crossover_creates_valid_offspring() {
+        assert_valid_offspring::<StandardChromosome<TestDomain>>(homologous_crossover);
+        assert_valid_offspring::<ReorderChromosome<TestDomain>>(homologous_crossover);
+        assert_valid_offspring::<DagChromosome<TestDomain>>(homologous_crossover);
+    }
+}
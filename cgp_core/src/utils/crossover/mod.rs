@@ -0,0 +1,2 @@
+pub mod crossover_algos;
+pub mod semantic_operators;
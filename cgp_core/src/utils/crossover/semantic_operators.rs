@@ -0,0 +1,372 @@
+#![allow(clippy::too_many_arguments)]
+
+use std::collections::HashMap;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use crate::genotype::{CgpNode, Genotype};
+use crate::global_params::CgpParameters as g_params;
+use crate::utils::crossover::crossover_algos;
+use crate::value_domain::{SemanticFunctions, ValueDomain};
+
+/// Number of computational nodes of the random subgraphs used by the semantic operators.
+const RANDOM_SUBGRAPH_SIZE: usize = 3;
+
+/// Geometric semantic crossover (Moraglio et al.): each child is the convex combination
+/// `p2 + sigmoid(r) * (p1 - p2)` of its parents with a random subgraph `r`, i.e. its output
+/// lies between the outputs of the parents for every sample. The active graphs of both
+/// parents are embedded into the grid of the first parent. If they do not fit, the parents are
+/// recombined by uniform crossover instead and false is returned.
+pub fn semantic_crossover<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                          rng: &mut ChaCha8Rng,
+                                                          population: &[G],
+                                                          new_population: &mut [G],
+                                                          child1_id: usize,
+                                                          child2_id: usize,
+                                                          parent1_id: usize,
+                                                          parent2_id: usize) -> bool {
+    let cross_chromo_1 = get_semantic_offspring(params, rng, &population[parent1_id], &population[parent2_id]);
+    let cross_chromo_2 = get_semantic_offspring(params, rng, &population[parent2_id], &population[parent1_id]);
+
+    match (cross_chromo_1, cross_chromo_2) {
+        (Some(cross_chromo_1), Some(cross_chromo_2)) => {
+            new_population[child1_id] = cross_chromo_1;
+            new_population[child2_id] = cross_chromo_2;
+            true
+        }
+        _ => {
+            crossover_algos::uniform_crossover(params, rng, population, new_population,
+                                               child1_id, child2_id, parent1_id, parent2_id);
+            false
+        }
+    }
+}
+
+/// Geometric semantic mutation: adds `sigmoid(r1) - sigmoid(r2)` with random subgraphs `r1`
+/// and `r2` to every output, so each output moves by less than 1 per sample. If the extended
+/// graph does not fit into the grid, single active mutation is used instead and false is
+/// returned.
+pub fn semantic_mutation<D: ValueDomain, G: Genotype<D>>(chromosome: &mut G,
+                                                         params: &g_params,
+                                                         rng: &mut ChaCha8Rng) -> bool {
+    let functions = get_semantic_functions(chromosome.domain());
+
+    let mut program = Program::new(params.nbr_inputs, chromosome.domain());
+    let outputs = program.push_active_graph(params, chromosome);
+
+    let random_node_1 = program.push_random_subgraph(chromosome.domain(), rng);
+    let random_node_2 = program.push_random_subgraph(chromosome.domain(), rng);
+    let step_1 = program.push(functions.sigmoid, &[random_node_1]);
+    let step_2 = program.push(functions.sigmoid, &[random_node_2]);
+    let step = program.push(functions.sub, &[step_1, step_2]);
+
+    let outputs: Vec<usize> = outputs.iter()
+        .map(|output| program.push(functions.add, &[*output, step]))
+        .collect();
+
+    if program.write_into(params, chromosome, &outputs, rng) {
+        true
+    } else {
+        chromosome.mutate_single(rng);
+        false
+    }
+}
+
+/// `None` if the combined program does not fit into the grid.
+fn get_semantic_offspring<D: ValueDomain, G: Genotype<D>>(params: &g_params,
+                                                          rng: &mut ChaCha8Rng,
+                                                          parent1: &G,
+                                                          parent2: &G) -> Option<G> {
+    let functions = get_semantic_functions(parent1.domain());
+
+    let mut program = Program::new(params.nbr_inputs, parent1.domain());
+    let outputs_p1 = program.push_active_graph(params, parent1);
+    let outputs_p2 = program.push_active_graph(params, parent2);
+
+    let random_node = program.push_random_subgraph(parent1.domain(), rng);
+    let weight = program.push(functions.sigmoid, &[random_node]);
+
+    let mut outputs = Vec::with_capacity(outputs_p1.len());
+    for (output_p1, output_p2) in outputs_p1.iter().zip(outputs_p2.iter()) {
+        let difference = program.push(functions.sub, &[*output_p1, *output_p2]);
+        let weighted_difference = program.push(functions.mul, &[weight, difference]);
+        outputs.push(program.push(functions.add, &[*output_p2, weighted_difference]));
+    }
+
+    let mut child = parent1.clone();
+    if program.write_into(params, &mut child, &outputs, rng) {
+        Some(child)
+    } else {
+        None
+    }
+}
+
+fn get_semantic_functions<D: ValueDomain>(domain: &D) -> SemanticFunctions {
+    domain.semantic_functions()
+        .expect("The semantic operators need add, sub, mul and sigmoid in the function set")
+}
+
+/// A graph assembled from parts of several chromosomes before it is written into a grid.
+/// Ids below `nbr_inputs` are program inputs, node `i` has the id `nbr_inputs + i`.
+/// Identical nodes are only stored once. As the offspring of semantic operators contain their
+/// parents, relatives share most of their nodes, which keeps the program small enough for the
+/// grid.
+struct Program {
+    nbr_inputs: usize,
+    max_arity: usize,
+    constant_function: Option<usize>,
+    /// Function id, connections and constant of every node, in evaluation order.
+    nodes: Vec<(usize, Vec<usize>, Option<f32>)>,
+    /// Function id, connections and bits of the constant of every node -> its id.
+    node_ids: HashMap<(usize, Vec<usize>, Option<u32>), usize>,
+}
+
+impl Program {
+    fn new<D: ValueDomain>(nbr_inputs: usize, domain: &D) -> Self {
+        Self {
+            nbr_inputs,
+            max_arity: domain.max_arity(),
+            constant_function: domain.constant_function(),
+            nodes: vec![],
+            node_ids: HashMap::new(),
+        }
+    }
+
+    /// Appends a node and returns its id. Connections that are not read use the first input.
+    fn push(&mut self, function_id: usize, inputs: &[usize]) -> usize {
+        self.push_with_constant(function_id, inputs, None)
+    }
+
+    /// Like `push`, a constant of `None` keeps the constant gene of the node it is written to.
+    /// Returns the id of an identical node if there is one. The constant only tells nodes
+    /// apart if they compute the constant function.
+    fn push_with_constant(&mut self, function_id: usize, inputs: &[usize], constant: Option<f32>) -> usize {
+        let mut connections = inputs.to_vec();
+        connections.resize(self.max_arity, 0);
+
+        let constant_bits = constant
+            .filter(|_| self.constant_function == Some(function_id))
+            .map(f32::to_bits);
+        let key = (function_id, connections.clone(), constant_bits);
+        if let Some(node_id) = self.node_ids.get(&key) {
+            return *node_id;
+        }
+
+        self.nodes.push((function_id, connections, constant));
+        let node_id = self.nbr_inputs + self.nodes.len() - 1;
+        self.node_ids.insert(key, node_id);
+        node_id
+    }
+
+    /// For every node, whether one of `outputs` depends on it.
+    fn get_used_nodes(&self, outputs: &[usize]) -> Vec<bool> {
+        let mut is_used = vec![false; self.nodes.len()];
+        for output in outputs.iter().filter(|output| **output >= self.nbr_inputs) {
+            is_used[*output - self.nbr_inputs] = true;
+        }
+
+        // connections point to earlier nodes, so one backward pass finds all dependencies
+        for index in (0..self.nodes.len()).rev() {
+            if !is_used[index] {
+                continue;
+            }
+            for connection in self.nodes[index].1.iter().filter(|connection| **connection >= self.nbr_inputs) {
+                is_used[*connection - self.nbr_inputs] = true;
+            }
+        }
+
+        is_used
+    }
+
+    /// Appends the active computational nodes of `chromosome` and returns the ids its output
+    /// nodes read from.
+    fn push_active_graph<D: ValueDomain, G: Genotype<D>>(&mut self, params: &g_params, chromosome: &G) -> Vec<usize> {
+        let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+        // only entries of inputs and active nodes are read
+        let mut program_ids: Vec<usize> = (0..first_output_id).collect();
+
+        for node_id in chromosome.active_nodes() {
+            if !(params.nbr_inputs..first_output_id).contains(node_id) {
+                continue;
+            }
+
+            let node = &chromosome.nodes_grid()[*node_id];
+            let arity = chromosome.domain().arity(node.function_id());
+            let inputs: Vec<usize> = node.connections()[..arity]
+                .iter()
+                .map(|connection| program_ids[*connection])
+                .collect();
+
+            program_ids[*node_id] = self.push_with_constant(node.function_id(), &inputs, node.constant());
+        }
+
+        chromosome.nodes_grid()[first_output_id..]
+            .iter()
+            .map(|node| program_ids[node.connections()[0]])
+            .collect()
+    }
+
+    /// Appends a random subgraph over the program inputs and returns the id of its last node.
+    fn push_random_subgraph<D: ValueDomain>(&mut self, domain: &D, rng: &mut ChaCha8Rng) -> usize {
+        let mut candidates: Vec<usize> = (0..self.nbr_inputs).collect();
+
+        for _ in 0..RANDOM_SUBGRAPH_SIZE {
+            let function_id = rng.gen_range(0..domain.nbr_functions());
+            let inputs: Vec<usize> = (0..domain.arity(function_id))
+                .map(|_| candidates[rng.gen_range(0..candidates.len())])
+                .collect();
+
+            let node_id = self.push(function_id, &inputs);
+            candidates.push(node_id);
+        }
+
+        *candidates.last().unwrap()
+    }
+
+    /// Writes the nodes `outputs` depend on into the computational nodes of `chromosome` and
+    /// connects its output nodes to `outputs`. Every node is placed at the first position
+    /// behind its predecessor from which all its connections are valid, so the order of
+    /// evaluation is kept. Returns false and leaves the chromosome unchanged if these nodes do
+    /// not fit into the grid.
+    fn write_into<D: ValueDomain, G: Genotype<D>>(&self,
+                                                  params: &g_params,
+                                                  chromosome: &mut G,
+                                                  outputs: &[usize],
+                                                  rng: &mut ChaCha8Rng) -> bool {
+        let first_output_id = params.nbr_inputs + params.nbr_computational_nodes;
+        let is_used = self.get_used_nodes(outputs);
+        if is_used.iter().filter(|used| **used).count() > params.nbr_computational_nodes {
+            return false;
+        }
+
+        // program id -> position in the grid; unused nodes get no position
+        let mut positions: Vec<usize> = (0..self.nbr_inputs).collect();
+        let mut next_position = params.nbr_inputs;
+        for ((_, connections, _), used) in self.nodes.iter().zip(&is_used) {
+            if !used {
+                positions.push(usize::MAX);
+                continue;
+            }
+
+            let position = (next_position..first_output_id).find(|position| {
+                connections.iter().all(|connection| chromosome.is_valid_connection(*position, positions[*connection]))
+            });
+
+            match position {
+                Some(position) => {
+                    positions.push(position);
+                    next_position = position + 1;
+                }
+                None => return false,
+            }
+        }
+
+        for (((function_id, connections, constant), position), _) in self.nodes.iter()
+            .zip(&positions[self.nbr_inputs..])
+            .zip(&is_used)
+            .filter(|(_, used)| **used) {
+            let node = &mut chromosome.nodes_grid_mut()[*position];
+            node.set_function_id(*function_id);
+            if constant.is_some() {
+                node.set_constant(*constant);
+            }
+            for (gene, connection) in node.connections_mut().iter_mut().zip(connections.iter()) {
+                *gene = positions[*connection];
+            }
+        }
+
+        for (output_node, output) in chromosome.nodes_grid_mut()[first_output_id..].iter_mut().zip(outputs.iter()) {
+            output_node.connections_mut()[0] = positions[*output];
+        }
+
+        chromosome.repair(rng);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+    use crate::standard_cgp::chromosome::Chromosome;
+    use crate::utils::evaluation_buffer::{ColumnStore, DatasetId};
+    use crate::utils::test_domain;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain};
+    use super::*;
+
+    #[test]
+    fn semantic_crossover_children_lie_between_parents() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let params = test_params(2, 2, 200, 1, 200);
+        let inputs = test_inputs(2, 8);
+        let mut column_store = ColumnStore::default();
+
+        let mut nbr_successes = 0;
+        for _ in 0..20 {
+            let mut parents: Vec<Chromosome<TestDomain>> = (0..2)
+                .map(|_| Chromosome::new(params.clone(), TestDomain { with_constant: false }, &mut rng))
+                .collect();
+            let parent_outputs: Vec<Vec<Vec<f32>>> = parents.iter_mut()
+                .map(|parent| parent.predict(&inputs, DatasetId::Training, &mut column_store))
+                .collect();
+
+            let mut children = parents.clone();
+            if !semantic_crossover(&params, &mut rng, &parents, &mut children, 0, 1, 0, 1) {
+                continue;
+            }
+            nbr_successes += 1;
+
+            for child in &mut children {
+                let child_outputs = child.predict(&inputs, DatasetId::Training, &mut column_store);
+                for (output, column) in child_outputs.iter().enumerate() {
+                    for (row, value) in column.iter().enumerate() {
+                        let bound_1 = parent_outputs[0][output][row];
+                        let bound_2 = parent_outputs[1][output][row];
+                        let tolerance = 1e-4 * bound_1.abs().max(bound_2.abs()).max(1.);
+                        assert!(*value >= bound_1.min(bound_2) - tolerance && *value <= bound_1.max(bound_2) + tolerance,
+                                "{} is not between {} and {}", value, bound_1, bound_2);
+                    }
+                }
+            }
+        }
+        assert!(nbr_successes > 0);
+    }
+
+    #[test]
+    fn program_stores_identical_nodes_once() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let params = test_params(2, 2, 50, 1, 50);
+        let mut chromosome = Chromosome::new(params.clone(), TestDomain { with_constant: true }, &mut rng);
+        chromosome.get_active_nodes_id();
+
+        let mut program = Program::new(params.nbr_inputs, chromosome.domain());
+        let outputs = program.push_active_graph(&params, &chromosome);
+        let nbr_nodes = program.nodes.len();
+
+        assert_eq!(program.push_active_graph(&params, &chromosome), outputs);
+        assert_eq!(program.nodes.len(), nbr_nodes);
+    }
+
+    #[test]
+    fn write_into_skips_unused_nodes() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        // room for 3 nodes, but the program has 4 of which one is unused
+        let params = test_params(2, 1, 3, 1, 3);
+        let domain = TestDomain { with_constant: false };
+        let mut chromosome = Chromosome::new(params.clone(), domain.clone(), &mut rng);
+
+        let mut program = Program::new(params.nbr_inputs, &domain);
+        let unused = program.push(test_domain::ADD, &[0, 1]);
+        let first = program.push(test_domain::SUB, &[0, 1]);
+        let second = program.push(test_domain::MUL, &[first, 1]);
+        let output = program.push(test_domain::ADD, &[second, first]);
+        assert_ne!(unused, first);
+
+        assert!(program.write_into(&params, &mut chromosome, &[output], &mut rng));
+        let inputs = test_inputs(2, 4);
+        let mut column_store = ColumnStore::default();
+        let outputs = chromosome.predict(&inputs, DatasetId::Training, &mut column_store);
+        for (row, value) in outputs[0].iter().enumerate() {
+            let difference = inputs[0][row] - inputs[1][row];
+            assert_eq!(*value, difference * inputs[1][row] + difference);
+        }
+    }
+}
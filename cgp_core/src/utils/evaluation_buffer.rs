@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use nohash_hasher::BuildNoHashHasher;
+use crate::utils::tape::{Opcode, Tape};
+use crate::value_domain::ValueDomain;
+
+/// Dataset a chromosome is evaluated on. Columns computed on one dataset are only reused by
+/// later runs on the same dataset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatasetId {
+    /// Training data of the runner, used for the fitness.
+    Training,
+    /// Unseen data of the runner, used for the test fitness.
+    Evaluation,
+}
+
+/// Column of a `ColumnStore`. Ids of freed columns are not valid anymore.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnId {
+    index: usize,
+    generation: u32,
+}
+
+/// Columns computed by the evaluation buffers of a population, owned by the runner and passed
+/// to every evaluation.
+///
+/// Buffers refer to the columns by `ColumnId`, so the clone of a chromosome shares the columns
+/// of the original. A column is never written after it was computed. `free_unused_columns`
+/// keeps the allocations of the columns no buffer of the population refers to for new columns;
+/// a buffer still holding the id of a freed column computes the column again.
+pub struct ColumnStore<V> {
+    columns: Vec<Vec<V>>,
+    /// Incremented when the column is freed, which invalidates its ids.
+    generations: Vec<u32>,
+    free_columns: Vec<usize>,
+}
+
+impl<V> Default for ColumnStore<V> {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            generations: Vec::new(),
+            free_columns: Vec::new(),
+        }
+    }
+}
+
+impl<V: Clone + Default> ColumnStore<V> {
+    /// Frees all columns that none of `buffers` refers to.
+    pub fn free_unused_columns<'a>(&mut self, buffers: impl IntoIterator<Item = &'a EvaluationBuffer<V>>)
+        where V: 'a {
+        let mut unused = vec![true; self.columns.len()];
+        for index in &self.free_columns {
+            unused[*index] = false;
+        }
+        for buffer in buffers {
+            for column_id in buffer.columns.iter().filter(|column_id| self.is_valid(**column_id)) {
+                unused[column_id.index] = false;
+            }
+        }
+
+        for (index, unused) in unused.into_iter().enumerate() {
+            if unused {
+                self.generations[index] = self.generations[index].wrapping_add(1);
+                self.free_columns.push(index);
+            }
+        }
+    }
+
+    /// Number of columns that are not freed.
+    pub fn nbr_used_columns(&self) -> usize {
+        self.columns.len() - self.free_columns.len()
+    }
+
+    fn is_valid(&self, column_id: ColumnId) -> bool {
+        self.generations.get(column_id.index) == Some(&column_id.generation)
+    }
+
+    fn column(&self, column_id: ColumnId) -> &[V] {
+        debug_assert!(self.is_valid(column_id));
+        &self.columns[column_id.index]
+    }
+
+    /// A new column and its `nbr_samples` values to compute, which are given back by `insert`.
+    fn allocate(&mut self, nbr_samples: usize) -> (ColumnId, Vec<V>) {
+        let index = self.free_columns.pop().unwrap_or_else(|| {
+            self.columns.push(Vec::new());
+            self.generations.push(0);
+            self.columns.len() - 1
+        });
+
+        let mut values = std::mem::take(&mut self.columns[index]);
+        values.resize(nbr_samples, V::default());
+        (ColumnId { index, generation: self.generations[index] }, values)
+    }
+
+    fn insert(&mut self, column_id: ColumnId, values: Vec<V>) {
+        self.columns[column_id.index] = values;
+    }
+}
+
+/// Expression a column was computed from. Its sources are registers of the last run tape.
+#[derive(Clone, Copy)]
+struct Expression {
+    hash: u64,
+    opcode: u64,
+    first_source: usize,
+    arity: usize,
+}
+
+/// Register file for running a `Tape` over a dataset.
+///
+/// The registers written by instructions are columns of a `ColumnStore`, input registers are
+/// read from the dataset in place. Clones share the columns, so an offspring starts with the
+/// columns of its parent. A run keeps the column of every instruction that computes the same
+/// expression from the same sources as in the last run, and only computes the others, i.e.
+/// the nodes downstream of the genes changed by a mutation or a crossover.
+pub struct EvaluationBuffer<V> {
+    columns: Vec<ColumnId>,
+    /// Expression of each column.
+    expressions: Vec<Expression>,
+    /// Sources of all expressions back to back.
+    expression_sources: Vec<usize>,
+    dataset: Option<DatasetId>,
+    nbr_inputs: usize,
+    nbr_samples: usize,
+    /// Column of the last run with the hash, rebuilt by every run. Only finds the candidate
+    /// column; it is reused if its opcode and sources match.
+    column_ids: HashMap<u64, usize, BuildNoHashHasher<u64>>,
+    values: PhantomData<V>,
+}
+
+impl<V> Default for EvaluationBuffer<V> {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            expressions: Vec::new(),
+            expression_sources: Vec::new(),
+            dataset: None,
+            nbr_inputs: 0,
+            nbr_samples: 0,
+            column_ids: HashMap::default(),
+            values: PhantomData,
+        }
+    }
+}
+
+impl<V> Clone for EvaluationBuffer<V> {
+    /// The columns are shared, the scratch allocations are not copied.
+    fn clone(&self) -> Self {
+        Self {
+            columns: self.columns.clone(),
+            expressions: self.expressions.clone(),
+            expression_sources: self.expression_sources.clone(),
+            dataset: self.dataset,
+            nbr_inputs: self.nbr_inputs,
+            nbr_samples: self.nbr_samples,
+            column_ids: HashMap::default(),
+            values: PhantomData,
+        }
+    }
+}
+
+impl<V: Clone + Default> EvaluationBuffer<V> {
+    /// Runs all instructions of `tape` on the columns of `inputs`, which is the dataset `dataset`.
+    /// New columns are allocated in `column_store`.
+    pub fn run<D: ValueDomain<Value = V>>(&mut self,
+                                          domain: &D,
+                                          tape: &Tape,
+                                          inputs: &[Vec<V>],
+                                          dataset: DatasetId,
+                                          column_store: &mut ColumnStore<V>) {
+        let nbr_samples = inputs[0].len();
+        if self.dataset != Some(dataset) || self.nbr_inputs != tape.nbr_inputs || self.nbr_samples != nbr_samples {
+            self.forget_columns();
+            self.dataset = Some(dataset);
+            self.nbr_inputs = tape.nbr_inputs;
+            self.nbr_samples = nbr_samples;
+        }
+
+        self.column_ids.clear();
+        for (column_id, expression) in self.expressions.iter().enumerate() {
+            self.column_ids.entry(expression.hash).or_insert(column_id);
+        }
+
+        // register of the last run computing the same expression as each register of the tape
+        let mut previous_registers: Vec<Option<usize>> = (0..tape.nbr_inputs).map(Some).collect();
+        let mut columns: Vec<ColumnId> = Vec::with_capacity(tape.instructions.len());
+
+        for instruction in &tape.instructions {
+            let sources = tape.sources(instruction);
+
+            let previous_column_id = self.column_ids.get(&instruction.hash)
+                .copied()
+                .filter(|column_id| {
+                    let expression = &self.expressions[*column_id];
+                    let previous_sources = &self.expression_sources[expression.first_source..expression.first_source + expression.arity];
+
+                    expression.opcode == instruction.opcode.word()
+                        && column_store.is_valid(self.columns[*column_id])
+                        && sources.iter().map(|source| previous_registers[*source]).eq(previous_sources.iter().map(|source| Some(*source)))
+                });
+            if let Some(column_id) = previous_column_id {
+                columns.push(self.columns[column_id]);
+                previous_registers.push(Some(tape.nbr_inputs + column_id));
+                continue;
+            }
+
+            let (column_id, mut values) = column_store.allocate(nbr_samples);
+            match instruction.opcode {
+                // only domains with a constant function, and thus a constant value, have constant nodes
+                Opcode::Constant(constant) => values.fill(domain.constant_value(constant).unwrap_or_default()),
+                Opcode::Function(function_id) => {
+                    // sources were written before, so their columns are in `columns`
+                    let node_inputs: Vec<&[V]> = sources.iter()
+                        .map(|source| read_register(inputs, &columns, column_store, tape.nbr_inputs, *source))
+                        .collect();
+                    domain.execute(function_id, &node_inputs, &mut values);
+                }
+            }
+            column_store.insert(column_id, values);
+
+            columns.push(column_id);
+            previous_registers.push(None);
+        }
+
+        self.columns = columns;
+        self.expression_sources.clear();
+        self.expressions.clear();
+        for instruction in &tape.instructions {
+            self.expressions.push(Expression {
+                hash: instruction.hash,
+                opcode: instruction.opcode.word(),
+                first_source: self.expression_sources.len(),
+                arity: instruction.arity,
+            });
+            self.expression_sources.extend_from_slice(tape.sources(instruction));
+        }
+    }
+
+    /// Columns of the output registers of `tape`, as computed by the last `run` on `inputs`.
+    pub fn output_columns<'a>(&self, tape: &Tape, inputs: &'a [Vec<V>], column_store: &'a ColumnStore<V>) -> Vec<&'a [V]> {
+        tape.outputs
+            .iter()
+            .map(|register| read_register(inputs, &self.columns, column_store, self.nbr_inputs, *register))
+            .collect()
+    }
+
+    fn forget_columns(&mut self) {
+        self.columns.clear();
+        self.expressions.clear();
+        self.expression_sources.clear();
+    }
+}
+
+fn read_register<'a, V: Clone + Default>(inputs: &'a [Vec<V>],
+                                         columns: &[ColumnId],
+                                         column_store: &'a ColumnStore<V>,
+                                         nbr_inputs: usize,
+                                         register: usize) -> &'a [V] {
+    match register.checked_sub(nbr_inputs) {
+        None => &inputs[register],
+        Some(column_id) => column_store.column(columns[column_id]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::SeedableRng;
+    use crate::genotype::{CgpNode, Genotype};
+    use crate::standard_cgp::chromosome::Chromosome;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain, ADD, MUL, SUB};
+    use super::*;
+
+    /// A chromosome with 2 inputs, 1 output and the computational nodes `nodes` as function
+    /// and connections, followed by inactive nodes.
+    fn build_chromosome(nodes: &[(usize, [usize; 2])], output: usize, rng: &mut ChaCha8Rng) -> Chromosome<TestDomain> {
+        let mut chromosome = Chromosome::new(test_params(2, 1, 4, 1, 4), TestDomain { with_constant: false }, rng);
+        let nodes_grid = chromosome.nodes_grid_mut();
+        for (node, (function_id, connections)) in nodes_grid[2..].iter_mut().zip(nodes.iter()) {
+            node.set_function_id(*function_id);
+            node.connections_mut()[..2].copy_from_slice(connections);
+        }
+        nodes_grid[6].connections_mut()[0] = output;
+        chromosome
+    }
+
+    fn columns(chromosome: &Chromosome<TestDomain>) -> &[ColumnId] {
+        &chromosome.phenotype().evaluation_buffer.columns
+    }
+
+    /// (x0 + x1) * x0 - x1, and its clone (x0 - x1) * x0 - x1 with the first node mutated.
+    fn parent_and_mutated_clone(column_store: &mut ColumnStore<f32>) -> (Chromosome<TestDomain>, Chromosome<TestDomain>) {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let inputs = test_inputs(2, 4);
+
+        let mut parent = build_chromosome(&[(ADD, [0, 1]), (MUL, [2, 0]), (SUB, [3, 1])], 4, &mut rng);
+        parent.evaluate_nodes(&inputs, DatasetId::Training, column_store);
+
+        let mut child = parent.clone();
+        child.nodes_grid_mut()[2].set_function_id(SUB);
+        child.evaluate_nodes(&inputs, DatasetId::Training, column_store);
+        (parent, child)
+    }
+
+    #[test]
+    fn unchanged_columns_are_shared_with_clones() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let inputs = test_inputs(2, 4);
+        let mut column_store = ColumnStore::default();
+
+        let mut parent = build_chromosome(&[(ADD, [0, 1]), (MUL, [2, 0]), (SUB, [3, 1])], 4, &mut rng);
+        parent.evaluate_nodes(&inputs, DatasetId::Training, &mut column_store);
+        assert_eq!(column_store.nbr_used_columns(), 3);
+
+        // the last node is mutated, the nodes before it are not computed again
+        let mut child = parent.clone();
+        child.nodes_grid_mut()[4].set_function_id(ADD);
+        let outputs = child.predict(&inputs, DatasetId::Training, &mut column_store);
+        assert_eq!(columns(&child)[..2], columns(&parent)[..2]);
+        assert_ne!(columns(&child)[2], columns(&parent)[2]);
+        assert_eq!(column_store.nbr_used_columns(), 4);
+
+        let mut fresh_child = build_chromosome(&[(ADD, [0, 1]), (MUL, [2, 0]), (ADD, [3, 1])], 4, &mut rng);
+        assert_eq!(fresh_child.predict(&inputs, DatasetId::Training, &mut ColumnStore::default()), outputs);
+    }
+
+    #[test]
+    fn mutated_node_invalidates_the_columns_downstream() {
+        let mut column_store = ColumnStore::default();
+        let (parent, child) = parent_and_mutated_clone(&mut column_store);
+
+        for (parent_column, child_column) in columns(&parent).iter().zip(columns(&child)) {
+            assert_ne!(parent_column, child_column);
+        }
+        assert_eq!(column_store.nbr_used_columns(), 6);
+    }
+
+    #[test]
+    fn freed_columns_are_computed_again() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let inputs = test_inputs(2, 4);
+        let mut column_store = ColumnStore::default();
+        let (mut parent, child) = parent_and_mutated_clone(&mut column_store);
+        let outputs = parent.predict(&inputs, DatasetId::Training, &mut column_store);
+
+        // frees the columns only the parent refers to, a new child computes into one of them
+        column_store.free_unused_columns([&child.phenotype().evaluation_buffer]);
+        assert_eq!(column_store.nbr_used_columns(), 3);
+        let mut other_child = child.clone();
+        other_child.nodes_grid_mut()[4].set_function_id(MUL);
+        other_child.evaluate_nodes(&inputs, DatasetId::Training, &mut column_store);
+
+        assert_eq!(parent.predict(&inputs, DatasetId::Training, &mut column_store), outputs);
+        let mut fresh_parent = build_chromosome(&[(ADD, [0, 1]), (MUL, [2, 0]), (SUB, [3, 1])], 4, &mut rng);
+        assert_eq!(fresh_parent.predict(&inputs, DatasetId::Training, &mut ColumnStore::default()), outputs);
+    }
+
+    #[test]
+    fn chromosomes_can_be_sent_to_other_threads() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Chromosome<TestDomain>>();
+    }
+}
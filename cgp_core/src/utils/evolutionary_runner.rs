@@ -0,0 +1,51 @@
+use crate::genotype::Genotype;
+use crate::value_domain::ValueDomain;
+
+/// Common interface of the evolutionary strategies.
+///
+/// Implemented by the (1+4)-ES, the (mu+lambda)-ES and the tournament selection with
+/// elitists, so the strategy is chosen at runtime via `--runner-type`.
+pub trait EvolutionaryRunner<D: ValueDomain, G: Genotype<D>> {
+    /// Runs one generation.
+    fn learn_step(&mut self, i: usize);
+
+    /// Best training fitness of the current population.
+    fn get_best_fitness(&self) -> f32;
+
+    fn get_best_solution(&self) -> G;
+
+    /// Tunes the constants of the best chromosome, see `constant_optimisation::optimise_constants`.
+    /// Uses at most `constant_optimisation_evaluations` of the evaluations left.
+    fn optimise_best_constants(&mut self);
+
+    /// Number of evaluations on the training data so far that computed the fitness, including
+    /// constant tuning. Evaluations answered by the fitness cache are not counted.
+    fn get_nbr_evaluations(&self) -> usize;
+
+    /// Number of pairs recombined by crossover. Always 0 for runners without crossover.
+    fn get_nbr_crossovers(&self) -> usize;
+
+    /// Semantic crossovers whose children did not fit into the grid, so uniform crossover was
+    /// used instead. Always 0 for runners without crossover.
+    fn get_nbr_semantic_crossover_failures(&self) -> usize;
+
+    /// Number of mutated children.
+    fn get_nbr_mutations(&self) -> usize;
+
+    /// Semantic mutations whose result did not fit into the grid, so single active mutation was
+    /// used instead.
+    fn get_nbr_semantic_mutation_failures(&self) -> usize;
+
+    /// Reorders that kept the nodes in place, because the equidistant positions would violate
+    /// the levels-back limit. Always 0 for variants without reordering.
+    fn get_nbr_reorder_skips(&self) -> usize;
+
+    /// Evaluations answered by the fitness cache, because the phenotype was evaluated before.
+    fn get_nbr_cache_hits(&self) -> usize;
+
+    /// Evaluations that computed the fitness.
+    fn get_nbr_cache_misses(&self) -> usize;
+
+    /// Best fitness of the current population on the evaluation data.
+    fn get_test_fitness(&mut self) -> f32;
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use nohash_hasher::BuildNoHashHasher;
+use crate::genotype::Genotype;
+use crate::utils::evaluation_buffer::ColumnStore;
+use crate::value_domain::ValueDomain;
+
+/// Default largest number of phenotypes in the cache, which bounds its memory.
+const CACHE_CAPACITY: usize = 20_000;
+
+/// A cached phenotype. The program is compared on every hit, so a collision of the phenotype
+/// hashes is a miss instead of a wrong fitness.
+struct CacheEntry<T> {
+    program: Box<[u64]>,
+    fitness: f32,
+    output_transform: T,
+    /// Value of `FitnessCache::clock` when the entry was last looked up or inserted.
+    last_used: usize,
+}
+
+/// Training fitness and fitted output transform per phenotype, keyed by the phenotype hash
+/// of the chromosome. Children whose active graph equals one evaluated before, e.g. after a
+/// mutation of an unused connection or a crossover of inactive nodes, are not evaluated again.
+///
+/// A full cache drops the phenotypes that were not used in the last `capacity / 2`
+/// evaluations, so phenotypes that keep being hit, e.g. of parents whose children often
+/// share their active graph, stay in the cache.
+pub struct FitnessCache<T> {
+    entries: HashMap<u64, CacheEntry<T>, BuildNoHashHasher<u64>>,
+    capacity: usize,
+    /// Number of evaluations so far, used as the age of the entries.
+    clock: usize,
+    pub nbr_hits: usize,
+    pub nbr_misses: usize,
+}
+
+impl<T: Clone> FitnessCache<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::default(),
+            capacity,
+            clock: 0,
+            nbr_hits: 0,
+            nbr_misses: 0,
+        }
+    }
+
+    /// Same result as `chromosome.evaluate(data, label, column_store)`, looked up if the
+    /// phenotype is known.
+    pub fn evaluate<D: ValueDomain<OutputTransform = T>, G: Genotype<D>>(&mut self,
+                                                                         chromosome: &mut G,
+                                                                         data: &[Vec<D::Value>],
+                                                                         label: &[Vec<D::Value>],
+                                                                         column_store: &mut ColumnStore<D::Value>) -> f32 {
+        self.clock += 1;
+        let tape = chromosome.tape();
+        let hash = tape.hash;
+
+        if let Some(entry) = self.entries.get_mut(&hash) {
+            if *entry.program == *tape.canonical_program() {
+                self.nbr_hits += 1;
+                entry.last_used = self.clock;
+                chromosome.set_output_transform(entry.output_transform.clone());
+                return entry.fitness;
+            }
+        }
+
+        self.nbr_misses += 1;
+        let fitness = chromosome.evaluate(data, label, column_store);
+
+        if self.entries.len() >= self.capacity {
+            let oldest_kept = self.clock.saturating_sub(self.capacity / 2);
+            self.entries.retain(|_, entry| entry.last_used >= oldest_kept);
+        }
+        let entry = CacheEntry {
+            program: chromosome.tape().canonical_program().into_boxed_slice(),
+            fitness,
+            output_transform: chromosome.output_transform().clone(),
+            last_used: self.clock,
+        };
+        self.entries.insert(hash, entry);
+        fitness
+    }
+}
+
+impl<T: Clone> Default for FitnessCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::SeedableRng;
+    use crate::genotype::CgpNode;
+    use crate::standard_cgp::chromosome::Chromosome;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain, ADD, MUL, SUB};
+    use super::*;
+
+    /// A chromosome with 2 inputs whose only output is `function_id` of `connections`.
+    fn build_chromosome(function_id: usize, connections: [usize; 2], rng: &mut ChaCha8Rng) -> Chromosome<TestDomain> {
+        let mut chromosome = Chromosome::new(test_params(2, 1, 1, 1, 1), TestDomain { with_constant: false }, rng);
+        let nodes_grid = chromosome.nodes_grid_mut();
+        nodes_grid[2].set_function_id(function_id);
+        nodes_grid[2].connections_mut()[..2].copy_from_slice(&connections);
+        nodes_grid[3].connections_mut()[0] = 2;
+        chromosome
+    }
+
+    #[test]
+    fn hit_is_not_evaluated_again() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let inputs = test_inputs(2, 4);
+        let labels = vec![vec![0.; 4]];
+        let mut column_store = ColumnStore::default();
+        let mut cache = FitnessCache::new();
+
+        let mut chromosome = build_chromosome(ADD, [0, 1], &mut rng);
+        let fitness = cache.evaluate(&mut chromosome, &inputs, &labels, &mut column_store);
+        assert_eq!((cache.nbr_hits, cache.nbr_misses), (0, 1));
+
+        // another chromosome with the same phenotype
+        let mut same_phenotype = build_chromosome(ADD, [0, 1], &mut rng);
+        assert_eq!(cache.evaluate(&mut same_phenotype, &inputs, &labels, &mut column_store), fitness);
+        assert_eq!((cache.nbr_hits, cache.nbr_misses), (1, 1));
+
+        let mut other = build_chromosome(MUL, [0, 1], &mut rng);
+        cache.evaluate(&mut other, &inputs, &labels, &mut column_store);
+        assert_eq!((cache.nbr_hits, cache.nbr_misses), (1, 2));
+    }
+
+    #[test]
+    fn hash_collision_is_a_miss() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let inputs = test_inputs(2, 4);
+        let labels = vec![vec![0.; 4]];
+        let mut column_store = ColumnStore::default();
+        let mut cache = FitnessCache::new();
+
+        let mut chromosome = build_chromosome(SUB, [0, 1], &mut rng);
+        let fitness = chromosome.evaluate(&inputs, &labels, &mut column_store);
+
+        // another program under the hash of the chromosome, with a wrong fitness
+        let mut other = build_chromosome(SUB, [1, 0], &mut rng);
+        cache.entries.insert(chromosome.tape().hash, CacheEntry {
+            program: other.tape().canonical_program().into_boxed_slice(),
+            fitness: fitness + 1.,
+            output_transform: (),
+            last_used: 0,
+        });
+
+        assert_eq!(cache.evaluate(&mut chromosome, &inputs, &labels, &mut column_store), fitness);
+        assert_eq!((cache.nbr_hits, cache.nbr_misses), (0, 1));
+    }
+
+    #[test]
+    fn full_cache_drops_the_entries_not_used_recently() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let inputs = test_inputs(2, 4);
+        let labels = vec![vec![0.; 4]];
+        let mut column_store = ColumnStore::default();
+        let mut cache = FitnessCache::with_capacity(4);
+
+        let mut chromosomes = [
+            build_chromosome(ADD, [0, 1], &mut rng),
+            build_chromosome(SUB, [0, 1], &mut rng),
+            build_chromosome(SUB, [1, 0], &mut rng),
+            build_chromosome(MUL, [0, 1], &mut rng),
+            build_chromosome(ADD, [0, 0], &mut rng),
+        ];
+        let hashes: Vec<u64> = chromosomes.iter_mut().map(|chromosome| chromosome.tape().hash).collect();
+
+        for chromosome in &mut chromosomes[..3] {
+            cache.evaluate(chromosome, &inputs, &labels, &mut column_store);
+        }
+        // keeps the first chromosome recent
+        cache.evaluate(&mut chromosomes[0], &inputs, &labels, &mut column_store);
+        cache.evaluate(&mut chromosomes[3], &inputs, &labels, &mut column_store);
+        assert_eq!(cache.entries.len(), 4);
+
+        // the cache is full, only the entries used in the last 2 evaluations are kept
+        cache.evaluate(&mut chromosomes[4], &inputs, &labels, &mut column_store);
+        let mut kept: Vec<u64> = cache.entries.keys().copied().collect();
+        kept.sort();
+        let mut expected = vec![hashes[0], hashes[3], hashes[4]];
+        expected.sort();
+        assert_eq!(kept, expected);
+        assert_eq!((cache.nbr_hits, cache.nbr_misses), (1, 5));
+    }
+}
@@ -0,0 +1,14 @@
+pub mod node_type;
+pub mod cycle_checker;
+pub mod evolutionary_runner;
+pub mod runner;
+pub mod crossover;
+pub mod utility_funcs;
+pub mod constant_optimisation;
+pub mod evaluation_buffer;
+pub mod fitness_cache;
+pub mod tape;
+pub mod runner_multiple_parents_with_elitist_tournament;
+pub mod runner_multiple_parents_with_elitist_mulambda;
+#[cfg(test)]
+pub mod test_domain;
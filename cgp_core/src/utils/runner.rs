@@ -0,0 +1,269 @@
+use std::fmt::{Display, Formatter};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use rand_core::SeedableRng;
+use crate::global_params::CgpParameters as g_params;
+use crate::utils::utility_funcs;
+use crate::genotype::Genotype;
+use crate::utils::constant_optimisation::{get_tuning_budget, optimise_constants};
+use crate::utils::evaluation_buffer::ColumnStore;
+use crate::utils::evolutionary_runner::EvolutionaryRunner;
+use crate::utils::fitness_cache::FitnessCache;
+use crate::value_domain::ValueDomain;
+
+
+pub struct Runner<D: ValueDomain, G: Genotype<D>> {
+    params: g_params,
+    data: Vec<Vec<D::Value>>,
+    label: Vec<Vec<D::Value>>,
+    eval_data: Vec<Vec<D::Value>>,
+    eval_label: Vec<Vec<D::Value>>,
+    /// Evaluations of the constant tuning. The fitness cache counts all other evaluations that
+    /// computed the fitness as misses.
+    nbr_tuning_evaluations: usize,
+    nbr_reorder_skips: usize,
+    nbr_mutations: usize,
+    /// Semantic mutations that did not fit into the grid and fell back to single active mutation.
+    nbr_semantic_mutation_failures: usize,
+    /// Fitness of the phenotypes evaluated so far.
+    fitness_cache: FitnessCache<D::OutputTransform>,
+    /// Columns of the evaluation buffers of the population.
+    column_store: ColumnStore<D::Value>,
+    population: Vec<G>,
+    best_fitness: f32,
+    pub fitness_vals: Vec<f32>,
+    parent_id: usize,
+    rng: ChaCha8Rng,
+}
+
+impl<D: ValueDomain, G: Genotype<D>> Display for Runner<D, G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Parent: {}", self.population[self.parent_id])?;
+        writeln!(f, "Fitness: {}", self.best_fitness)
+    }
+}
+
+impl<D: ValueDomain, G: Genotype<D>> Runner<D, G> {
+    /// `data` and `eval_data` are column-wise: one vector per input node.
+    /// `label` and `eval_label` hold one vector per output node.
+    pub fn new(params: g_params,
+               domain: D,
+               data: Vec<Vec<D::Value>>,
+               label: Vec<Vec<D::Value>>,
+               eval_data: Vec<Vec<D::Value>>,
+               eval_label: Vec<Vec<D::Value>>, ) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(params.seed);
+        let mut fitness_cache = FitnessCache::new();
+        let mut column_store = ColumnStore::default();
+
+        let mut chromosomes: Vec<G> = Vec::with_capacity(params.mu + params.lambda);
+        let mut fitness_vals: Vec<f32> = Vec::with_capacity(params.mu + params.lambda);
+
+        for _ in 0..(params.mu + params.lambda) {
+            let mut chromosome = G::new(params.clone(), domain.clone(), &mut rng);
+            let mut fitness = fitness_cache.evaluate(&mut chromosome, &data, &label, &mut column_store);
+            if !fitness.is_finite() {
+                fitness = f32::MAX;
+            }
+            fitness_vals.push(fitness);
+
+            chromosomes.push(chromosome);
+        }
+
+        let best_fitness = utility_funcs::get_min(&fitness_vals);
+        let parent_id = utility_funcs::get_argmin(&fitness_vals);
+
+
+        Self {
+            params,
+            data,
+            label,
+            eval_data,
+            eval_label,
+            nbr_tuning_evaluations: 0,
+            nbr_reorder_skips: 0,
+            nbr_mutations: 0,
+            nbr_semantic_mutation_failures: 0,
+            fitness_cache,
+            column_store,
+            population: chromosomes,
+            best_fitness,
+            fitness_vals,
+            parent_id,
+            rng,
+        }
+    }
+
+    fn reorder(&mut self) {
+        // the parent should not be reordered as it did not change
+        for i in 0..(self.params.mu + self.params.lambda) {
+            if i == self.parent_id {
+                continue;
+            }
+            if !self.population[i].reorder(&mut self.rng) {
+                self.nbr_reorder_skips += 1;
+            }
+        }
+    }
+
+    fn new_parent_by_neutral_search(&mut self) {
+        let mut min_keys: Vec<usize> = Vec::with_capacity(self.params.mu + self.params.lambda);
+
+        utility_funcs::get_argmins_of_value(&self.fitness_vals, &mut min_keys, self.best_fitness);
+
+        if min_keys.len() == 1 {
+            self.parent_id = min_keys[0];
+        } else {
+            if min_keys.contains(&self.parent_id) {
+                let index = min_keys.iter().position(|x| *x == self.parent_id).unwrap();
+                min_keys.remove(index);
+            }
+            self.parent_id = *min_keys.choose(&mut self.rng).unwrap();
+        }
+    }
+
+    fn mutate_chromosomes(&mut self) {
+        // mutate new chromosomes; do not mutate parent
+        for i in 0..(self.params.mu + self.params.lambda) {
+            if i == self.parent_id {
+                continue;
+            }
+            self.population[i] = self.population[self.parent_id].clone();
+
+            self.nbr_mutations += 1;
+            if !self.population[i].mutate(&self.params, &mut self.rng) {
+                self.nbr_semantic_mutation_failures += 1;
+            }
+        }
+    }
+
+    fn eval_chromosomes(&mut self) {
+        for i in 0..(self.params.mu + self.params.lambda) {
+            if i != self.parent_id {
+                let mut fitness: f32 = self.fitness_cache.evaluate(&mut self.population[i], &self.data, &self.label, &mut self.column_store);
+                if !fitness.is_finite() {
+                    fitness = f32::MAX;
+                }
+
+                self.fitness_vals[i] = fitness;
+            }
+        }
+
+        let best_fitness = utility_funcs::get_min(&self.fitness_vals);
+
+        self.best_fitness = best_fitness;
+
+        // the columns of the replaced chromosomes are only needed for new columns
+        self.column_store.free_unused_columns(self.population.iter().map(|chromosome| &chromosome.phenotype().evaluation_buffer));
+    }
+
+    pub fn get_elitism_fitness(&self) -> Vec<f32> {
+        vec![self.best_fitness]
+    }
+
+}
+
+impl<D: ValueDomain, G: Genotype<D>> EvolutionaryRunner<D, G> for Runner<D, G> {
+    fn learn_step(&mut self, _i: usize) {
+        self.reorder();
+
+        self.mutate_chromosomes();
+
+        self.eval_chromosomes();
+
+        self.new_parent_by_neutral_search();
+    }
+
+    fn get_best_fitness(&self) -> f32 {
+        self.best_fitness
+    }
+
+    fn optimise_best_constants(&mut self) {
+        let budget = get_tuning_budget(&self.params, self.get_nbr_evaluations());
+        let (fitness, nbr_evaluations) = optimise_constants(&mut self.population[self.parent_id],
+                                                            &self.data,
+                                                            &self.label,
+                                                            self.best_fitness,
+                                                            budget,
+                                                            &mut self.column_store);
+        self.fitness_vals[self.parent_id] = fitness;
+        self.best_fitness = fitness;
+        self.nbr_tuning_evaluations += nbr_evaluations;
+    }
+
+    fn get_nbr_evaluations(&self) -> usize {
+        self.fitness_cache.nbr_misses + self.nbr_tuning_evaluations
+    }
+
+    fn get_nbr_crossovers(&self) -> usize {
+        0
+    }
+
+    fn get_nbr_semantic_crossover_failures(&self) -> usize {
+        0
+    }
+
+    fn get_nbr_mutations(&self) -> usize {
+        self.nbr_mutations
+    }
+
+    fn get_nbr_semantic_mutation_failures(&self) -> usize {
+        self.nbr_semantic_mutation_failures
+    }
+
+    fn get_nbr_reorder_skips(&self) -> usize {
+        self.nbr_reorder_skips
+    }
+
+    fn get_nbr_cache_hits(&self) -> usize {
+        self.fitness_cache.nbr_hits
+    }
+
+    fn get_nbr_cache_misses(&self) -> usize {
+        self.fitness_cache.nbr_misses
+    }
+
+    fn get_test_fitness(&mut self) -> f32 {
+        let mut best_fitness = f32::MAX;
+
+        for individual in &mut self.population {
+            let fitness = individual.evaluate_test(&self.eval_data, &self.eval_label, &mut self.column_store);
+
+            if !fitness.is_nan() && fitness < best_fitness {
+                best_fitness = fitness;
+            }
+        }
+        best_fitness
+    }
+
+    fn get_best_solution(&self) -> G {
+        self.population[self.parent_id].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::standard_cgp::chromosome::Chromosome;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain};
+    use super::*;
+
+    #[test]
+    fn cache_hits_are_not_counted_as_evaluations() {
+        let params = test_params(2, 1, 30, 1, 30);
+        let inputs = test_inputs(2, 4);
+        let labels = vec![vec![0.; 4]];
+        let mut runner: Runner<TestDomain, Chromosome<TestDomain>> = Runner::new(
+            params.clone(), TestDomain { with_constant: false }, inputs.clone(), labels.clone(), inputs, labels);
+
+        let nbr_steps = 50;
+        for i in 0..nbr_steps {
+            runner.learn_step(i);
+        }
+
+        // every child is looked up in the cache; mutations of inactive nodes are hits
+        let nbr_lookups = params.mu + params.lambda + nbr_steps * (params.mu + params.lambda - 1);
+        assert!(runner.get_nbr_cache_hits() > 0);
+        assert_eq!(runner.get_nbr_evaluations(), runner.get_nbr_cache_misses());
+        assert_eq!(runner.get_nbr_evaluations() + runner.get_nbr_cache_hits(), nbr_lookups);
+    }
+}
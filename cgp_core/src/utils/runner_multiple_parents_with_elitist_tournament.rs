@@ -0,0 +1,374 @@
+use std::fmt::{Display, Formatter};
+use rand::prelude::IteratorRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use rand_core::SeedableRng;
+use crate::global_params::CgpParameters as g_params;
+use crate::utils::utility_funcs::{get_argmin, get_argmins_of_value, vect_difference};
+use crate::utils::crossover::crossover_algos;
+use crate::genotype::Genotype;
+use crate::utils::constant_optimisation::{get_tuning_budget, optimise_constants};
+use crate::utils::evaluation_buffer::ColumnStore;
+use crate::utils::evolutionary_runner::EvolutionaryRunner;
+use crate::utils::fitness_cache::FitnessCache;
+use crate::value_domain::ValueDomain;
+
+// ID's: Begin with population, afterwards elitsts.
+// Total number of population: population.len + elitsits.len
+// Andere Idee:
+// größere Population: Population <- population + elitisten
+// speichere elitist id
+
+pub struct Runner<D: ValueDomain, G: Genotype<D>> {
+    pub params: g_params,
+    data: Vec<Vec<D::Value>>,
+    label: Vec<Vec<D::Value>>,
+    eval_data: Vec<Vec<D::Value>>,
+    eval_label: Vec<Vec<D::Value>>,
+    /// Evaluations of the constant tuning. The fitness cache counts all other evaluations that
+    /// computed the fitness as misses.
+    nbr_tuning_evaluations: usize,
+    /// Fitness of the phenotypes evaluated so far.
+    fitness_cache: FitnessCache<D::OutputTransform>,
+    /// Columns of the evaluation buffers of the population.
+    column_store: ColumnStore<D::Value>,
+    nbr_crossovers: usize,
+    /// Semantic crossovers that did not fit into the grid and fell back to uniform crossover.
+    nbr_semantic_crossover_failures: usize,
+    nbr_mutations: usize,
+    /// Semantic mutations that did not fit into the grid and fell back to single active mutation.
+    nbr_semantic_mutation_failures: usize,
+    nbr_reorder_skips: usize,
+    pub population: Vec<G>,
+    pub fitness_vals_sorted: Vec<f32>,
+    // check for correctness, must include elitists too
+    pub fitness_vals: Vec<f32>,
+    // check for correctness, must include elitists too
+    pub tournament_selected: Vec<usize>,
+    pub rng: ChaCha8Rng,
+
+    pub elitist_ids: Vec<usize>,
+    pub child_ids: Vec<usize>,
+}
+
+impl<D: ValueDomain, G: Genotype<D>> Display for Runner<D, G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fitnesses: {:?}", self.fitness_vals)
+    }
+}
+
+impl<D: ValueDomain, G: Genotype<D>> Runner<D, G> {
+    /// `data` and `eval_data` are column-wise: one vector per input node.
+    /// `label` and `eval_label` hold one vector per output node.
+    pub fn new(params: g_params,
+               domain: D,
+               data: Vec<Vec<D::Value>>,
+               label: Vec<Vec<D::Value>>,
+               eval_data: Vec<Vec<D::Value>>,
+               eval_label: Vec<Vec<D::Value>>, ) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(params.seed);
+        let mut fitness_cache = FitnessCache::new();
+        let mut column_store = ColumnStore::default();
+
+        let mut population: Vec<G> = Vec::with_capacity(params.population_size + params.elitism_number);
+        let mut fitness_vals: Vec<f32> = Vec::with_capacity(params.population_size + params.elitism_number);
+
+        for _ in 0..(params.population_size + params.elitism_number) {
+            let mut chromosome = G::new(params.clone(), domain.clone(), &mut rng);
+            let mut fitness = fitness_cache.evaluate(&mut chromosome, &data, &label, &mut column_store);
+
+            if !fitness.is_finite() {
+                fitness = f32::MAX;
+            }
+
+            fitness_vals.push(fitness);
+            population.push(chromosome);
+        }
+
+        // Get sorted fitness vals
+        let mut fitness_vals_sorted = fitness_vals.clone();
+        fitness_vals_sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // Reverse fitness_vals_sorted to pop the best fitness first
+        let mut temp_fitness_vals_sorted = fitness_vals_sorted.clone();
+        temp_fitness_vals_sorted.reverse();
+        temp_fitness_vals_sorted.dedup();
+
+
+        let mut elitist_ids: Vec<usize> = vec![];
+
+        while elitist_ids.len() < params.elitism_number {
+            let current_best_fitness_val = temp_fitness_vals_sorted.pop().unwrap();
+
+            get_argmins_of_value(&fitness_vals,
+                                 &mut elitist_ids,
+                                 current_best_fitness_val);
+        }
+
+        elitist_ids.truncate(params.elitism_number);
+
+
+        let child_ids: Vec<usize> = (0..(params.population_size + params.elitism_number)).collect();
+        let child_ids = vect_difference(&child_ids, &elitist_ids);
+
+
+
+        Self {
+            params,
+            data,
+            label,
+            eval_data,
+            eval_label,
+            nbr_tuning_evaluations: 0,
+            fitness_cache,
+            column_store,
+            nbr_crossovers: 0,
+            nbr_semantic_crossover_failures: 0,
+            nbr_mutations: 0,
+            nbr_semantic_mutation_failures: 0,
+            nbr_reorder_skips: 0,
+            population,
+            fitness_vals,
+            fitness_vals_sorted,
+            rng,
+            elitist_ids,
+            tournament_selected: vec![],
+            child_ids
+        }
+    }
+
+    fn get_child_ids(&mut self) {
+        let child_ids: Vec<usize> = (0..(self.params.population_size + self.params.elitism_number)).collect();
+        let child_ids = vect_difference(&child_ids, &self.elitist_ids);
+
+        self.child_ids = child_ids;
+    }
+
+
+    fn reorder(&mut self) {
+        // elitists should not be reordered as they did not change
+        for id in &self.child_ids {
+            if !self.population[*id].reorder(&mut self.rng) {
+                self.nbr_reorder_skips += 1;
+            }
+        }
+    }
+
+
+    fn tournament_selection(&mut self) {
+        let mut selection = vec![];
+
+        // take pop-size many, as pop-size many will be replaced
+        for _ in 0..self.params.population_size {
+            let winner_id = self.fitness_vals
+                .clone()
+                .into_iter()
+                .enumerate() // get tuples: (i, fitness_val) with i := chromosome id
+                .choose_multiple(&mut self.rng, self.params.tournament_size)
+                .into_iter()
+                .min_by(|i, j| i.1.partial_cmp(&j.1).unwrap())  // Sort by fitness val
+                .map(|(i, _)| i)// get id of chromosome
+                .unwrap();
+
+            selection.push(winner_id)
+        }
+
+        self.tournament_selected = selection;
+    }
+
+    fn mutate_chromosomes(&mut self) {
+        // mutate new chromosomes; do not mutate elitists
+        for id in &self.child_ids {
+            self.nbr_mutations += 1;
+            if !self.population[*id].mutate(&self.params, &mut self.rng) {
+                self.nbr_semantic_mutation_failures += 1;
+            }
+        }
+    }
+
+    fn eval_chromosomes(&mut self) {
+        for id in &self.child_ids {
+            let mut fitness: f32 = self.fitness_cache.evaluate(&mut self.population[*id], &self.data, &self.label, &mut self.column_store);
+
+            if !fitness.is_finite() {
+                fitness = f32::MAX;
+            }
+
+            self.fitness_vals[*id] = fitness;
+        }
+
+        let mut best_fitnesses_sorted = self.fitness_vals.clone();
+        best_fitnesses_sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        self.fitness_vals_sorted = best_fitnesses_sorted;
+
+        // the columns of the replaced chromosomes are only needed for new columns
+        self.column_store.free_unused_columns(self.population.iter().map(|chromosome| &chromosome.phenotype().evaluation_buffer));
+    }
+
+    fn get_elitists(&mut self) {
+        let mut temp_fitness_vals_sorted = self.fitness_vals_sorted.clone();
+        // reverse to pop the last element - the best one
+        temp_fitness_vals_sorted.dedup();
+        temp_fitness_vals_sorted.reverse();
+
+        let mut elitist_ids: Vec<usize> = vec![];
+
+        while elitist_ids.len() < self.params.elitism_number {
+            let current_best_fitness_val = temp_fitness_vals_sorted.pop().unwrap();
+
+            get_argmins_of_value(&self.fitness_vals,
+                                 &mut elitist_ids,
+                                 current_best_fitness_val);
+        }
+
+        elitist_ids.truncate(self.params.elitism_number);
+        self.elitist_ids = elitist_ids;
+    }
+
+    /// Tunes the constants of the chromosomes `ids`, see `constant_optimisation::optimise_constants`.
+    fn optimise_constants_of(&mut self, ids: &[usize]) {
+        for id in ids {
+            let budget = get_tuning_budget(&self.params, self.get_nbr_evaluations());
+            let (fitness, nbr_evaluations) = optimise_constants(&mut self.population[*id],
+                                                                &self.data,
+                                                                &self.label,
+                                                                self.fitness_vals[*id],
+                                                                budget,
+                                                                &mut self.column_store);
+            self.fitness_vals[*id] = fitness;
+            self.nbr_tuning_evaluations += nbr_evaluations;
+        }
+
+        let mut best_fitnesses_sorted = self.fitness_vals.clone();
+        best_fitnesses_sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        self.fitness_vals_sorted = best_fitnesses_sorted;
+    }
+
+    pub fn get_elitism_fitness(&self) -> Vec<f32> {
+        let mut results: Vec<f32> = Vec::with_capacity(self.params.elitism_number);
+        for id in &self.elitist_ids {
+            results.push(self.fitness_vals[*id]);
+        }
+        results
+    }
+
+    fn crossover(&mut self) {
+        // get all new children ids; i.e. the ID's of chromosomes in the population that
+        // can be replaced.
+        // It must exclude the elitists, otherwise they may be replaced too
+        let children_set: Vec<usize> = (0..(self.params.population_size + self.params.elitism_number)).collect();
+        let children_set: Vec<usize> = vect_difference(&children_set, &self.elitist_ids);
+
+        // create new population
+        let mut new_population: Vec<G> = self.population.clone();
+
+        for (i, child_ids) in children_set.chunks(2).enumerate() {
+            let crossover_prob = self.rng.gen::<f32>();
+            if crossover_prob <= self.params.crossover_rate {
+                self.nbr_crossovers += 1;
+                let success = crossover_algos::crossover(&self.params,
+                                                         self.params.crossover_type,
+                                                         &mut self.rng,
+                                                         &self.population,
+                                                         &mut new_population,
+                                                         child_ids[0],
+                                                         child_ids[1],
+                                                         self.tournament_selected[2 * i],
+                                                         self.tournament_selected[2 * i + 1]);
+                if !success {
+                    self.nbr_semantic_crossover_failures += 1;
+                }
+            } else {
+                //     no crossover, just copy parents
+                new_population[child_ids[0]] = self.population[self.tournament_selected[2 * i]].clone();
+                new_population[child_ids[1]] = self.population[self.tournament_selected[2 * i + 1]].clone();
+            }
+        }
+        self.population = new_population;
+    }
+}
+
+impl<D: ValueDomain, G: Genotype<D>> EvolutionaryRunner<D, G> for Runner<D, G> {
+    fn learn_step(&mut self, i: usize) {
+        self.get_child_ids();
+
+        self.tournament_selection();
+
+        self.reorder();
+
+        self.crossover();
+
+        self.mutate_chromosomes();
+
+        self.eval_chromosomes();
+
+        self.get_elitists();
+
+        let interval = self.params.constant_optimisation_interval;
+        if interval > 0 && (i + 1).is_multiple_of(interval) {
+            let elitist_ids = self.elitist_ids.clone();
+            self.optimise_constants_of(&elitist_ids);
+        }
+    }
+
+    fn get_best_fitness(&self) -> f32 {
+        self.fitness_vals_sorted[0]
+    }
+
+    fn optimise_best_constants(&mut self) {
+        let best_id = get_argmin(&self.fitness_vals);
+        self.optimise_constants_of(&[best_id]);
+    }
+
+    fn get_nbr_evaluations(&self) -> usize {
+        self.fitness_cache.nbr_misses + self.nbr_tuning_evaluations
+    }
+
+    fn get_nbr_crossovers(&self) -> usize {
+        self.nbr_crossovers
+    }
+
+    fn get_nbr_semantic_crossover_failures(&self) -> usize {
+        self.nbr_semantic_crossover_failures
+    }
+
+    fn get_nbr_mutations(&self) -> usize {
+        self.nbr_mutations
+    }
+
+    fn get_nbr_semantic_mutation_failures(&self) -> usize {
+        self.nbr_semantic_mutation_failures
+    }
+
+    fn get_nbr_reorder_skips(&self) -> usize {
+        self.nbr_reorder_skips
+    }
+
+    fn get_nbr_cache_hits(&self) -> usize {
+        self.fitness_cache.nbr_hits
+    }
+
+    fn get_nbr_cache_misses(&self) -> usize {
+        self.fitness_cache.nbr_misses
+    }
+
+    fn get_test_fitness(&mut self) -> f32 {
+        let mut best_fitness = f32::MAX;
+
+        for individual in &mut self.population {
+            let fitness = individual.evaluate_test(&self.eval_data, &self.eval_label, &mut self.column_store);
+
+            if !fitness.is_nan() && fitness < best_fitness {
+                best_fitness = fitness;
+            }
+        }
+        best_fitness
+    }
+
+    fn get_best_solution(&self) -> G {
+        let idx = get_argmin(&self.fitness_vals);
+        self.population[idx].clone()
+    }
+}
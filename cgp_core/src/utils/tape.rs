@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+use nohash_hasher::BuildNoHashHasher;
+use crate::genotype::CgpNode;
+use crate::utils::node_type::NodeType;
+use crate::value_domain::ValueDomain;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Opcode {
+    /// Applies the function with this id of the domain.
+    Function(usize),
+    /// Fills the destination with the value of a constant node.
+    Constant(f32),
+}
+
+impl Opcode {
+    /// The opcode as one word, constants are compared by their bits.
+    pub fn word(&self) -> u64 {
+        match self {
+            Opcode::Function(function_id) => (*function_id as u64) << 1,
+            Opcode::Constant(constant) => ((constant.to_bits() as u64) << 1) | 1,
+        }
+    }
+}
+
+/// One step of the register machine: reads its `arity` source registers and writes the result
+/// into the destination register. The sources are stored in `Tape::sources`, starting at
+/// `first_source`.
+#[derive(Clone, Copy, Debug)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub first_source: usize,
+    pub arity: usize,
+    pub destination: usize,
+    /// Computational node the instruction was compiled from, used to name it.
+    pub node_id: usize,
+    /// Canonical hash of the expression computed by the instruction. It only depends on the
+    /// expression, not on the position of the nodes or the order of the instructions.
+    pub hash: u64,
+}
+
+/// The active graph of a chromosome compiled into a linear register program.
+///
+/// Registers `0..nbr_inputs` hold the input columns, the destination of instruction `i` is
+/// register `nbr_inputs + i`. Instructions only read registers written before, so running
+/// them in order evaluates the graph. Inactive nodes are not compiled.
+#[derive(Clone, Debug, Default)]
+pub struct Tape {
+    pub nbr_inputs: usize,
+    pub instructions: Vec<Instruction>,
+    /// Source registers of all instructions back to back, so functions of any arity fit.
+    pub sources: Vec<usize>,
+    /// Register each output node reads from, in the order of the output nodes.
+    pub outputs: Vec<usize>,
+    /// Canonical hash of the phenotype, combines the hashes of the output registers.
+    pub hash: u64,
+}
+
+impl Tape {
+    /// Compiles the computational nodes of `active_nodes`, which must be sorted such that
+    /// every node comes after the nodes it is connected to. Input nodes precede all other
+    /// nodes in `nodes_grid`.
+    pub fn compile<D: ValueDomain, N: CgpNode>(domain: &D, nodes_grid: &[N], active_nodes: &[usize]) -> Self {
+        let nbr_inputs = nodes_grid.iter()
+            .take_while(|node| *node.node_type() == NodeType::InputNode)
+            .count();
+
+        // register of each compiled node, indexed by node id
+        let mut registers: Vec<usize> = (0..nodes_grid.len()).collect();
+        let mut instructions: Vec<Instruction> = Vec::with_capacity(active_nodes.len());
+        let mut sources: Vec<usize> = Vec::with_capacity(active_nodes.len() * domain.max_arity());
+        let mut register_hashes: Vec<u64> = (0..nbr_inputs).map(input_hash).collect();
+
+        let constant_function = domain.constant_function();
+        for node_id in active_nodes {
+            let node = &nodes_grid[*node_id];
+            if *node.node_type() != NodeType::ComputationalNode {
+                continue;
+            }
+
+            let (opcode, arity) = if constant_function == Some(node.function_id()) {
+                (Opcode::Constant(node.constant().unwrap()), 0)
+            } else {
+                (Opcode::Function(node.function_id()), domain.arity(node.function_id()))
+            };
+
+            let first_source = sources.len();
+            sources.extend(node.connections()[..arity].iter().map(|connection| registers[*connection]));
+
+            let hash = instruction_hash(opcode, sources[first_source..].iter().map(|source| register_hashes[*source]));
+            register_hashes.push(hash);
+
+            let destination = nbr_inputs + instructions.len();
+            registers[*node_id] = destination;
+            instructions.push(Instruction {
+                opcode,
+                first_source,
+                arity,
+                destination,
+                node_id: *node_id,
+                hash,
+            });
+        }
+
+        let outputs: Vec<usize> = nodes_grid.iter()
+            .filter(|node| *node.node_type() == NodeType::OutputNode)
+            .map(|node| registers[node.connections()[0]])
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        for output in &outputs {
+            register_hashes[*output].hash(&mut hasher);
+        }
+        let hash = hasher.finish();
+
+        Self {
+            nbr_inputs,
+            instructions,
+            sources,
+            outputs,
+            hash,
+        }
+    }
+
+    /// Source registers `instruction` reads from.
+    pub fn sources(&self, instruction: &Instruction) -> &[usize] {
+        &self.sources[instruction.first_source..instruction.first_source + instruction.arity]
+    }
+
+    /// The expressions of the outputs as words, independent of node positions, the order of
+    /// the instructions and nodes that compute the same expression twice. Each distinct
+    /// expression is the word of its opcode followed by the ids of its sources, in the order
+    /// of a depth-first walk from the outputs. Inputs have the ids `0..nbr_inputs`, the k-th
+    /// expression the id `nbr_inputs + k`. The ids of the outputs and their number close the
+    /// list. Two tapes with equal words compute the same outputs.
+    pub fn canonical_program(&self) -> Vec<u64> {
+        let mut words: Vec<u64> = Vec::with_capacity(self.sources.len() + 2 * self.instructions.len());
+        // canonical id of each register, inputs are their own id
+        let mut canonical_ids: Vec<Option<u64>> = (0..self.nbr_inputs as u64).map(Some).collect();
+        canonical_ids.resize(self.nbr_inputs + self.instructions.len(), None);
+        // first word and canonical id of an expression with the hash, to merge equal expressions
+        let mut expression_starts: HashMap<u64, (usize, u64), BuildNoHashHasher<u64>> = HashMap::default();
+        let mut nbr_expressions = 0;
+
+        let mut to_visit: Vec<usize> = self.outputs.iter().rev().copied().collect();
+        while let Some(register) = to_visit.last().copied() {
+            if canonical_ids[register].is_some() {
+                to_visit.pop();
+                continue;
+            }
+
+            let instruction = &self.instructions[register - self.nbr_inputs];
+            let sources = self.sources(instruction);
+            // sources come first, in their order
+            let nbr_to_visit = to_visit.len();
+            to_visit.extend(sources.iter().rev().filter(|source| canonical_ids[**source].is_none()));
+            if to_visit.len() > nbr_to_visit {
+                continue;
+            }
+            to_visit.pop();
+
+            let start = words.len();
+            words.push(instruction.opcode.word());
+            words.extend(sources.iter().map(|source| canonical_ids[*source].unwrap()));
+
+            let length = words.len() - start;
+            let equal = expression_starts.get(&instruction.hash)
+                .copied()
+                .filter(|(equal_start, _)| words[*equal_start..*equal_start + length] == words[start..]);
+            match equal {
+                Some((_, canonical_id)) => {
+                    canonical_ids[register] = Some(canonical_id);
+                    words.truncate(start);
+                }
+                None => {
+                    let canonical_id = (self.nbr_inputs + nbr_expressions) as u64;
+                    expression_starts.entry(instruction.hash).or_insert((start, canonical_id));
+                    canonical_ids[register] = Some(canonical_id);
+                    nbr_expressions += 1;
+                }
+            }
+        }
+
+        words.extend(self.outputs.iter().map(|output| canonical_ids[*output].unwrap()));
+        words.push(self.outputs.len() as u64);
+        words
+    }
+
+    /// One assignment per instruction and output, e.g. `n5 = (x0 + x1)`. Inputs are named
+    /// `x<i>`, computational nodes `n<position>` and outputs `y<i>`.
+    pub fn display<D: ValueDomain>(&self, domain: &D, output_transform: &D::OutputTransform) -> String {
+        let name = |register: usize| match register.checked_sub(self.nbr_inputs) {
+            None => format!("x{}", register),
+            Some(instruction_id) => format!("n{}", self.instructions[instruction_id].node_id),
+        };
+
+        let mut program = String::new();
+        for instruction in &self.instructions {
+            match instruction.opcode {
+                Opcode::Constant(constant) => {
+                    writeln!(program, "{} = {}", name(instruction.destination), constant)
+                }
+                Opcode::Function(function_id) => {
+                    let inputs: Vec<String> = self.sources(instruction)
+                        .iter()
+                        .map(|source| name(*source))
+                        .collect();
+                    writeln!(program, "{} = {}", name(instruction.destination), domain.display_function(function_id, &inputs))
+                }
+            }.unwrap();
+        }
+        for (output_id, register) in self.outputs.iter().enumerate() {
+            let output = domain.display_output(output_id, name(*register), output_transform);
+            writeln!(program, "y{} = {}", output_id, output).unwrap();
+        }
+        program
+    }
+}
+
+fn input_hash(input_id: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    input_id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes an instruction from its opcode and the hashes of its sources, so equal expressions
+/// get equal hashes regardless of where their nodes are and unused genes are ignored.
+fn instruction_hash(opcode: Opcode, source_hashes: impl Iterator<Item = u64>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    match opcode {
+        Opcode::Function(function_id) => (0u8, function_id as u64).hash(&mut hasher),
+        Opcode::Constant(constant) => (1u8, constant.to_bits() as u64).hash(&mut hasher),
+    }
+    for source_hash in source_hashes {
+        source_hash.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::SeedableRng;
+    use crate::dag::chromosome_dag::Chromosome as DagChromosome;
+    use crate::genotype::Genotype;
+    use crate::standard_cgp::chromosome::Chromosome as StandardChromosome;
+    use crate::utils::test_domain::{test_inputs, test_params, TestDomain, ADD, MUL, SUB};
+    use super::*;
+
+    /// Value of node `node_id` for one sample, computed recursively from the genes.
+    fn evaluate_node<G: Genotype<TestDomain>>(chromosome: &G, node_id: usize, sample: &[f32]) -> f32 {
+        let domain = chromosome.domain();
+        let node = &chromosome.nodes_grid()[node_id];
+        match node.node_type() {
+            NodeType::InputNode => sample[node_id],
+            NodeType::OutputNode => evaluate_node(chromosome, node.connections()[0], sample),
+            NodeType::ComputationalNode => {
+                if domain.constant_function() == Some(node.function_id()) {
+                    return node.constant().unwrap();
+                }
+                let values: Vec<[f32; 1]> = node.connections()[..domain.arity(node.function_id())]
+                    .iter()
+                    .map(|connection| [evaluate_node(chromosome, *connection, sample)])
+                    .collect();
+                let inputs: Vec<&[f32]> = values.iter().map(|value| value.as_slice()).collect();
+
+                let mut output = [0.];
+                domain.execute(node.function_id(), &inputs, &mut output);
+                output[0]
+            }
+        }
+    }
+
+    /// Runs the tape register by register.
+    fn run_tape(tape: &Tape, domain: &TestDomain, inputs: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let mut registers: Vec<Vec<f32>> = inputs.to_vec();
+        for instruction in &tape.instructions {
+            let mut output = vec![0.; inputs[0].len()];
+            match instruction.opcode {
+                Opcode::Constant(constant) => output.fill(constant),
+                Opcode::Function(function_id) => {
+                    let sources: Vec<&[f32]> = tape.sources(instruction).iter().map(|source| registers[*source].as_slice()).collect();
+                    domain.execute(function_id, &sources, &mut output);
+                }
+            }
+            registers.push(output);
+        }
+        tape.outputs.iter().map(|output| registers[*output].clone()).collect()
+    }
+
+    fn assert_tape_matches_nodes<G: Genotype<TestDomain>>() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let params = test_params(3, 2, 30, 1, 30);
+        let inputs = test_inputs(3, 6);
+
+        for _ in 0..20 {
+            let mut chromosome = G::new(params.clone(), TestDomain { with_constant: true }, &mut rng);
+            let tape = chromosome.tape().clone();
+
+            let outputs = run_tape(&tape, chromosome.domain(), &inputs);
+            for (output, output_node_id) in outputs.iter().zip(params.nbr_inputs + params.nbr_computational_nodes..) {
+                for (row, value) in output.iter().enumerate() {
+                    let sample: Vec<f32> = inputs.iter().map(|input| input[row]).collect();
+                    assert_eq!(*value, evaluate_node(&chromosome, output_node_id, &sample));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tape_matches_node_evaluation() {
+        assert_tape_matches_nodes::<StandardChromosome<TestDomain>>();
+        assert_tape_matches_nodes::<DagChromosome<TestDomain>>();
+    }
+
+    #[test]
+    fn inactive_nodes_are_not_compiled() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let params = test_params(2, 1, 30, 1, 30);
+
+        for _ in 0..20 {
+            let mut chromosome = StandardChromosome::new(params.clone(), TestDomain { with_constant: true }, &mut rng);
+            let tape = chromosome.tape().clone();
+
+            let active_computational_nodes: Vec<usize> = chromosome.active_nodes()
+                .iter()
+                .copied()
+                .filter(|node_id| *chromosome.nodes_grid()[*node_id].node_type() == NodeType::ComputationalNode)
+                .collect();
+            let compiled_nodes: Vec<usize> = tape.instructions.iter().map(|instruction| instruction.node_id).collect();
+            assert_eq!(compiled_nodes, active_computational_nodes);
+
+            // changing the genes of an inactive node changes neither the tape nor its hash
+            let inactive_node_id = (params.nbr_inputs..params.nbr_inputs + params.nbr_computational_nodes)
+                .find(|node_id| !chromosome.active_nodes().contains(node_id));
+            if let Some(inactive_node_id) = inactive_node_id {
+                chromosome.mutate_node(inactive_node_id, &mut rng);
+                let mutated_tape = chromosome.tape();
+                assert_eq!(mutated_tape.hash, tape.hash);
+                assert_eq!(mutated_tape.canonical_program(), tape.canonical_program());
+            }
+        }
+    }
+
+    /// A chromosome with 2 inputs, 1 output and the computational nodes `nodes` as function
+    /// and connections, followed by inactive nodes.
+    fn build_chromosome(nodes: &[(usize, [usize; 2])], output: usize, rng: &mut ChaCha8Rng) -> StandardChromosome<TestDomain> {
+        let mut chromosome = StandardChromosome::new(test_params(2, 1, 6, 1, 6), TestDomain { with_constant: false }, rng);
+        let nodes_grid = chromosome.nodes_grid_mut();
+        for (node, (function_id, connections)) in nodes_grid[2..].iter_mut().zip(nodes.iter()) {
+            node.set_function_id(*function_id);
+            node.connections_mut()[..2].copy_from_slice(connections);
+        }
+        nodes_grid[8].connections_mut()[0] = output;
+        chromosome
+    }
+
+    #[test]
+    fn canonical_program_ignores_positions_and_duplicates() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        // (x0 + x1) - (x0 * x1), with the nodes in different positions
+        let mut chromosome = build_chromosome(&[(ADD, [0, 1]), (MUL, [0, 1]), (SUB, [2, 3])], 4, &mut rng);
+        let mut reordered = build_chromosome(&[(MUL, [0, 1]), (SUB, [0, 0]), (ADD, [0, 1]), (SUB, [4, 2])], 5, &mut rng);
+        assert_eq!(chromosome.tape().canonical_program(), reordered.tape().canonical_program());
+        assert_eq!(chromosome.phenotype_hash(), reordered.phenotype_hash());
+
+        // (x0 * x1) - (x0 + x1)
+        let mut swapped = build_chromosome(&[(ADD, [0, 1]), (MUL, [0, 1]), (SUB, [3, 2])], 4, &mut rng);
+        assert_ne!(chromosome.tape().canonical_program(), swapped.tape().canonical_program());
+
+        // (x0 + x1) * (x0 + x1), once with a node computing x0 + x1 twice
+        let mut shared = build_chromosome(&[(ADD, [0, 1]), (MUL, [2, 2])], 3, &mut rng);
+        let mut duplicated = build_chromosome(&[(ADD, [0, 1]), (ADD, [0, 1]), (MUL, [2, 3])], 4, &mut rng);
+        assert_eq!(shared.tape().canonical_program(), duplicated.tape().canonical_program());
+    }
+}
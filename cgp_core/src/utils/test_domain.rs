@@ -0,0 +1,95 @@
+use crate::global_params::CgpParameters;
+use crate::value_domain::{SemanticFunctions, ValueDomain};
+
+pub const ADD: usize = 0;
+pub const SUB: usize = 1;
+pub const MUL: usize = 2;
+pub const SIGMOID: usize = 3;
+/// `x0 * x1 + x2`, the only function with three inputs.
+pub const FMA: usize = 4;
+/// Only part of the function set if the domain was created with a constant.
+pub const CONSTANT: usize = 5;
+
+/// Arithmetic on f32 with functions of the arities 1, 2 and 3 for the tests of the core.
+/// The fitness is the mean absolute error, summed over the outputs.
+#[derive(Clone)]
+pub struct TestDomain {
+    pub with_constant: bool,
+}
+
+impl ValueDomain for TestDomain {
+    type Value = f32;
+    type OutputTransform = ();
+
+    fn nbr_functions(&self) -> usize {
+        if self.with_constant { CONSTANT + 1 } else { CONSTANT }
+    }
+
+    fn arity(&self, function_id: usize) -> usize {
+        match function_id {
+            SIGMOID => 1,
+            FMA => 3,
+            CONSTANT => 0,
+            _ => 2,
+        }
+    }
+
+    fn execute(&self, function_id: usize, inputs: &[&[f32]], output: &mut [f32]) {
+        for (row, value) in output.iter_mut().enumerate() {
+            *value = match function_id {
+                ADD => inputs[0][row] + inputs[1][row],
+                SUB => inputs[0][row] - inputs[1][row],
+                MUL => inputs[0][row] * inputs[1][row],
+                SIGMOID => 1. / (1. + (-inputs[0][row]).exp()),
+                FMA => inputs[0][row] * inputs[1][row] + inputs[2][row],
+                _ => panic!("not a function with inputs"),
+            };
+        }
+    }
+
+    fn constant_function(&self) -> Option<usize> {
+        self.with_constant.then_some(CONSTANT)
+    }
+
+    fn constant_value(&self, constant: f32) -> Option<f32> {
+        self.constant_function().map(|_| constant)
+    }
+
+    fn semantic_functions(&self) -> Option<SemanticFunctions> {
+        Some(SemanticFunctions {
+            add: ADD,
+            sub: SUB,
+            mul: MUL,
+            sigmoid: SIGMOID,
+        })
+    }
+
+    fn fitness(&self, outputs: &[&[f32]], labels: &[Vec<f32>]) -> f32 {
+        outputs.iter()
+            .zip(labels.iter())
+            .map(|(output, label)| {
+                output.iter().zip(label.iter()).map(|(x, y)| (x - y).abs()).sum::<f32>() / output.len() as f32
+            })
+            .sum()
+    }
+}
+
+/// Parameters of a grid with `nbr_nodes` computational nodes in `nbr_rows` rows.
+pub fn test_params(nbr_inputs: usize, nbr_outputs: usize, nbr_nodes: usize, nbr_rows: usize, levels_back: usize) -> CgpParameters {
+    CgpParameters {
+        nbr_inputs,
+        nbr_outputs,
+        nbr_computational_nodes: nbr_nodes,
+        nbr_rows,
+        nbr_columns: nbr_nodes / nbr_rows,
+        levels_back,
+        ..CgpParameters::default()
+    }
+}
+
+/// Columns of `nbr_inputs` inputs over `nbr_samples` samples with distinct values.
+pub fn test_inputs(nbr_inputs: usize, nbr_samples: usize) -> Vec<Vec<f32>> {
+    (0..nbr_inputs)
+        .map(|input| (0..nbr_samples).map(|row| 0.5 + input as f32 - 0.25 * row as f32).collect())
+        .collect()
+}
@@ -0,0 +1,107 @@
+use float_eq::float_eq;
+use std::collections::HashSet;
+use std::ops::Range;
+use rand::distributions::{Distribution, Uniform};
+use rand_chacha::ChaCha8Rng;
+
+/// Appends the ids of the values equal to `comp_value` up to the tolerance that are not in
+/// `res` yet, so a value close to two compared values is only added once.
+pub fn get_argmins_of_value(vecs: &[f32], res: &mut Vec<usize>, comp_value: f32) {
+    vecs.iter()
+        .enumerate()
+        .for_each(|(i, v)| {
+            if float_eq!(*v, comp_value, abs <= 0.000_1) && !res.contains(&i) {
+                res.push(i);
+            }
+        });
+}
+
+pub fn get_argmin(nets: &[f32]) -> usize {
+    nets.iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+        .unwrap()
+}
+
+pub fn get_min(nets: &[f32]) -> f32 {
+    *nets.iter()
+        .min_by(|a, b| a.partial_cmp(b)
+            .unwrap())
+        .unwrap()
+}
+
+
+pub fn vect_difference(v1: &[usize], v2: &[usize]) -> Vec<usize> {
+    let s1: HashSet<usize, nohash_hasher::BuildNoHashHasher<usize>> = v1.iter().cloned().collect();
+    let s2: HashSet<usize, nohash_hasher::BuildNoHashHasher<usize>> = v2.iter().cloned().collect();
+    (&s1 - &s2).iter().cloned().collect()
+}
+
+
+/// Draws a number from `0..upper_range` that differs from `excluded`.
+pub fn gen_random_number_for_node(excluded: usize, upper_range: usize, rng: &mut ChaCha8Rng) -> usize {
+    if upper_range <= 1 {
+        return 0;
+    }
+
+    let between = Uniform::from(0..upper_range);
+
+    loop {
+        let rand_nbr: usize = between.sample(rng);
+        if rand_nbr != excluded {
+            return rand_nbr;
+        }
+    }
+}
+
+/// Computational nodes a node at `position` may connect to: the nodes of the `levels_back`
+/// columns in front of its own column. The program inputs are always valid connections.
+pub fn levels_back_range(position: usize, nbr_inputs: usize, nbr_rows: usize, levels_back: usize) -> Range<usize> {
+    let column = (position - nbr_inputs) / nbr_rows;
+    let first_column = column.saturating_sub(levels_back);
+
+    (nbr_inputs + first_column * nbr_rows)..(nbr_inputs + column * nbr_rows)
+}
+
+/// Draws a connection from the program inputs and `allowed` that differs from `excluded`.
+pub fn gen_random_connection_in_range(excluded: usize,
+                                      nbr_inputs: usize,
+                                      allowed: Range<usize>,
+                                      rng: &mut ChaCha8Rng) -> usize {
+    let nbr_candidates = nbr_inputs + allowed.len();
+    let to_position = |index: usize| {
+        if index < nbr_inputs {
+            index
+        } else {
+            allowed.start + index - nbr_inputs
+        }
+    };
+
+    if nbr_candidates <= 1 {
+        return to_position(0);
+    }
+
+    let between = Uniform::from(0..nbr_candidates);
+
+    loop {
+        let rand_nbr: usize = to_position(between.sample(rng));
+        if rand_nbr != excluded {
+            return rand_nbr;
+        }
+    }
+}
+
+pub fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    assert!(!v.is_empty());
+    let len = v[0].len();
+    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
+    (0..len)
+        .map(|_| {
+            iters
+                .iter_mut()
+                .map(|n| n.next().unwrap())
+                .collect::<Vec<T>>()
+        })
+        .collect()
+}
@@ -0,0 +1,87 @@
+/// Function ids of the arithmetic the geometric semantic operators combine chromosomes with.
+#[derive(Clone, Copy)]
+pub struct SemanticFunctions {
+    pub add: usize,
+    pub sub: usize,
+    pub mul: usize,
+    pub sigmoid: usize,
+}
+
+/// The problem specific part of CGP.
+///
+/// A domain defines the type of the values flowing through the graph, the function set the
+/// computational nodes can choose from and how the outputs of a chromosome are scored.
+/// Everything else (genotype, mutation, crossover, runners) is shared between domains.
+///
+/// Data is passed column-wise: `inputs[i]` holds the values of input `i` for the whole
+/// dataset, `labels[o]` the expected values of output `o`.
+pub trait ValueDomain: Clone {
+    type Value: Clone + Default + 'static;
+
+    /// Parameters fitted to the training data and applied to the outputs before scoring, e.g.
+    /// the coefficients of linear scaling. `()` for domains that score the raw outputs.
+    type OutputTransform: Clone + Default;
+
+    /// Number of functions in the function set. Function ids are `0..nbr_functions()`.
+    fn nbr_functions(&self) -> usize;
+
+    /// Number of connections a function reads. At least 1, except for the constant function.
+    fn arity(&self, function_id: usize) -> usize;
+
+    /// Largest arity of the function set. Every computational node stores this many
+    /// connections and only reads the first `arity(function_id)` of them.
+    fn max_arity(&self) -> usize {
+        (0..self.nbr_functions())
+            .map(|function_id| self.arity(function_id))
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Applies function `function_id` element-wise and writes the result into `output`;
+    /// `inputs` holds `arity(function_id)` columns of the same length as `output`.
+    fn execute(&self, function_id: usize, inputs: &[&[Self::Value]], output: &mut [Self::Value]);
+
+    /// Readable form of function `function_id` applied to `inputs`, e.g. `(x0 + x1)`.
+    fn display_function(&self, function_id: usize, inputs: &[String]) -> String {
+        format!("f{}({})", function_id, inputs.join(", "))
+    }
+
+    /// Id of the constant function, if the function set has one. It reads no connections and
+    /// outputs the constant gene of its node; all other functions must have an arity of at least 1.
+    fn constant_function(&self) -> Option<usize> {
+        None
+    }
+
+    /// Value of a constant node with the constant gene `constant`. Domains with a constant
+    /// function must return `Some`, all others `None`.
+    fn constant_value(&self, _constant: f32) -> Option<Self::Value> {
+        None
+    }
+
+    /// Functions used by the geometric semantic operators. `None` if the function set lacks
+    /// one of them or the domain has no arithmetic.
+    fn semantic_functions(&self) -> Option<SemanticFunctions> {
+        None
+    }
+
+    /// Fitness of the output columns w.r.t. the labels. Lower is better, 0 is perfect.
+    fn fitness(&self, outputs: &[&[Self::Value]], labels: &[Vec<Self::Value>]) -> f32;
+
+    /// Fits the output transform to the labels of the training data.
+    fn fit_output_transform(&self, _outputs: &[&[Self::Value]], _labels: &[Vec<Self::Value>]) -> Self::OutputTransform {
+        Self::OutputTransform::default()
+    }
+
+    /// Fitness of the output columns after applying `output_transform`.
+    fn transformed_fitness(&self,
+                           outputs: &[&[Self::Value]],
+                           labels: &[Vec<Self::Value>],
+                           _output_transform: &Self::OutputTransform) -> f32 {
+        self.fitness(outputs, labels)
+    }
+
+    /// Readable form of output `output_id` reading `input`, after applying `output_transform`.
+    fn display_output(&self, _output_id: usize, input: String, _output_transform: &Self::OutputTransform) -> String {
+        input
+    }
+}
@@ -1,2 +0,0 @@
-pub mod node_dag;
-pub mod chromosome_dag;
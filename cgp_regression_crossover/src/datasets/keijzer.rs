@@ -1,16 +1,16 @@
 use crate::utils::utility_funcs::float_loop;
 
-fn make_label(inputs: &Vec<Vec<f32>>) -> Vec<f32> {
-    let mut labels: Vec<f32> = vec![];
+fn make_label(inputs: &Vec<Vec<f32>>) -> Vec<Vec<f32>> {
+    let mut labels: Vec<Vec<f32>> = vec![];
     for d in inputs {
-        labels.push(d.iter().map(|x| 1. / x).sum::<f32>());
+        labels.push(vec![d.iter().map(|x| 1. / x).sum::<f32>()]);
     }
 
     return labels;
 }
 
 
-pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
+pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
     let mut data = vec![];
 
     for x in float_loop(1., 50., 1.) {
@@ -25,7 +25,7 @@ pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
     return (data, labels);
 }
 
-pub fn get_eval_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
+pub fn get_eval_dataset() -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
     let mut data = vec![];
 
     for x in float_loop(1., 120., 1.) {
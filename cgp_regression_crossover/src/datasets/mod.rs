@@ -1,4 +1,10 @@
+// the benchmark datasets keep their original style, so `git blame` points at their authors
+#[allow(clippy::needless_return, clippy::vec_init_then_push)]
 pub mod keijzer;
+#[allow(clippy::needless_return, clippy::vec_init_then_push)]
 pub mod koza_3;
+#[allow(clippy::needless_return, clippy::vec_init_then_push)]
 pub mod nguyen_7;
-pub mod pagie_1;
\ No newline at end of file
+#[allow(clippy::needless_return, clippy::vec_init_then_push)]
+pub mod pagie_1;
+pub mod polynomial_system;
@@ -1,26 +1,26 @@
 use rand::distributions::{Distribution, Uniform};
+use rand_chacha::ChaCha8Rng;
 
 
 
-fn make_label(inputs: &Vec<Vec<f32>>) -> Vec<f32>{
-    let mut labels: Vec<f32> = vec![];
+fn make_label(inputs: &Vec<Vec<f32>>) -> Vec<Vec<f32>>{
+    let mut labels: Vec<Vec<f32>> = vec![];
     for d in inputs {
-        labels.push(d[0].ln_1p()  + d[0].powf(2.0).ln_1p());
+        labels.push(vec![d[0].ln_1p()  + d[0].powf(2.0).ln_1p()]);
     }
 
     return labels;
 }
 
 
-pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
+pub fn get_dataset(rng: &mut ChaCha8Rng) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
     let mut data = vec![];
 
     let between = Uniform::new(0.0, 2.0);
-    let mut rng = rand::thread_rng();
 
     for _ in 0..20 {
         let mut elem: Vec<f32> = vec![];
-        elem.push(between.sample(&mut rng));
+        elem.push(between.sample(rng));
 
         data.push(elem);
     }
@@ -31,6 +31,6 @@ pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
     return (data, labels);
 }
 
-pub fn get_eval_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
-    return get_dataset();
+pub fn get_eval_dataset(rng: &mut ChaCha8Rng) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+    return get_dataset(rng);
 }
@@ -1,17 +1,17 @@
 use crate::utils::utility_funcs::float_loop;
 
 
-fn make_label(inputs: &Vec<Vec<f32>>) -> Vec<f32> {
-    let mut labels: Vec<f32> = vec![];
+fn make_label(inputs: &Vec<Vec<f32>>) -> Vec<Vec<f32>> {
+    let mut labels: Vec<Vec<f32>> = vec![];
     for d in inputs {
-        labels.push(1. / (1. + d[0].powf(-4.)) + 1. / (1. + d[1].powf(-4.)));
+        labels.push(vec![1. / (1. + d[0].powf(-4.)) + 1. / (1. + d[1].powf(-4.))]);
     }
 
     return labels;
 }
 
 
-pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
+pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
     let mut data = vec![];
 
     for x in float_loop(-5., 5., 0.4) {
@@ -31,7 +31,7 @@ pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
     return (data, labels);
 }
 
-pub fn get_eval_dataset() -> (Vec<Vec<f32>>, Vec<f32>) {
+pub fn get_eval_dataset() -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
     let (data, labels) = get_dataset();
 
     return (data, labels);
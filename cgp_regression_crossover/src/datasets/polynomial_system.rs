@@ -0,0 +1,44 @@
+use crate::utils::utility_funcs::float_loop;
+
+// system of two equations sharing the inputs:
+// f1(x, y) = x^2 + x * y
+// f2(x, y) = y^2 - x
+fn make_label(inputs: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    let mut labels: Vec<Vec<f32>> = vec![];
+    for d in inputs {
+        labels.push(vec![d[0].powf(2.0) + d[0] * d[1], d[1].powf(2.0) - d[0]]);
+    }
+
+    labels
+}
+
+fn make_grid(start: f32, end: f32, step: f32) -> Vec<Vec<f32>> {
+    let mut data = vec![];
+
+    for x in float_loop(start, end, step) {
+        for y in float_loop(start, end, step) {
+            let elem: Vec<f32> = vec![x, y];
+
+            data.push(elem);
+        }
+    }
+
+    data
+}
+
+
+pub fn get_dataset() -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+    let data = make_grid(-1., 1., 0.2);
+
+    let labels = make_label(&data);
+
+    (data, labels)
+}
+
+pub fn get_eval_dataset() -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+    let data = make_grid(-0.9, 0.9, 0.2);
+
+    let labels = make_label(&data);
+
+    (data, labels)
+}
@@ -1,21 +1,28 @@
-use float_eq::float_eq;
 use std::io::Write;
-use cgp_regression_crossover::global_params::CgpParameters;
+use cgp_core::global_params::CgpParameters;
+use cgp_core::genotype::Genotype;
+use cgp_core::value_domain::ValueDomain;
+use cgp_core::standard_cgp::chromosome::Chromosome as StandardChromosome;
+use cgp_core::reorder::chromosome_reorder_equidistant::Chromosome as ReorderChromosome;
+use cgp_core::dag::chromosome_dag::Chromosome as DagChromosome;
+use cgp_core::utils::utility_funcs::transpose;
+use cgp_core::utils::evaluation_buffer::{ColumnStore, DatasetId};
 use cgp_regression_crossover::datasets::*;
+use cgp_regression_crossover::regression_domain::RegressionDomain;
+use cgp_regression_crossover::utils::fitness_metrics::{get_metric, FitnessReduction, DEFAULT_METRIC, METRIC_REGISTRY};
+use cgp_regression_crossover::utils::symbolic_regression_functions::{get_function_set, DEFAULT_FUNCTIONS};
 use clap::Parser;
+use rand_chacha::ChaCha8Rng;
+use rand_core::SeedableRng;
 use std::fs;
 use std::fs::File;
 use std::path::Path;
-use std::process::exit;
-use rayon::prelude::*;
 
 
-#[cfg(feature = "mulambda")]
-use cgp_regression_crossover::utils::runner::Runner;
-#[cfg(feature = "tournament")]
-use cgp_regression_crossover::utils::runner_multiple_parents_with_elitist_tournament::Runner;
-#[cfg(feature = "mulambda_crossover")]
-use cgp_regression_crossover::utils::runner_multiple_parents_with_elitist_mulambda::Runner;
+use cgp_core::utils::evolutionary_runner::EvolutionaryRunner;
+use cgp_core::utils::runner::Runner as OnePlusFourRunner;
+use cgp_core::utils::runner_multiple_parents_with_elitist_mulambda::Runner as MuLambdaRunner;
+use cgp_core::utils::runner_multiple_parents_with_elitist_tournament::Runner as TournamentRunner;
 
 #[derive(Parser, Clone)]
 #[clap(author, version, about, name = "testname")]
@@ -29,8 +36,17 @@ struct Args {
     #[arg(long, default_value_t = 450)]
     nbr_nodes: usize,
 
-    //         0 => format!("Baseline_standard"),
-    //         1 => format!("ereorder"),
+    // the computational nodes form a grid with nbr_rows rows; nbr_nodes must be divisible by it
+    #[arg(long, default_value_t = 1)]
+    nbr_rows: usize,
+
+    // number of preceding columns a node may connect to; 0: unrestricted
+    #[arg(long, default_value_t = 0)]
+    levels_back: usize,
+
+    // 0: standard CGP
+    // 1: CGP with E-Reorder
+    // 2: DAG-CGP; connections may point to any node as long as the graph stays acyclic
     #[arg(long, default_value_t = 1)]
     cgp_type: usize,
 
@@ -38,12 +54,24 @@ struct Args {
     // 1: multi-n crossover
     // 2: uniform crossover
     // 3: no crossover
+    // 4: subgraph crossover
+    // 5: discrete crossover; swaps single genes
+    // 6: block crossover; swaps a block of active nodes
+    // 7: output swap crossover; swaps the cones of single output nodes
+    // 8: homologous crossover; aligns parents by the rank of their active nodes
+    // 9: geometric semantic crossover; needs add, sub, mul and sigmoid in the function set.
+    //    Children contain both parents, so they outgrow small grids; the rate of fallbacks to
+    //    uniform crossover is part of the run output
     #[arg(long, default_value_t = 2)]
     crossover_type: usize,
 
     #[arg(long, default_value_t = 0.9)]
     crossover_rate: f32,
 
+    // point, multi-n and uniform crossover exchange output nodes as well
+    #[arg(long)]
+    crossover_include_outputs: bool,
+
     #[arg(long, default_value_t = 4)]
     tournament_size: usize,
 
@@ -57,8 +85,82 @@ struct Args {
     #[arg(long, default_value_t = 3)]
     multi_point_n: usize,
 
+    // 0: (1+4)-ES
+    // 1: (mu+lambda)-ES with crossover; mu = elitism_number, lambda = population_size
+    // 2: tournament selection with elitists
     #[arg(long, default_value_t = 1)]
     runner_type: usize,
+
+    // 0: single active mutation
+    // 1: probabilistic point mutation; every gene is mutated with mutation_rate
+    // 2: mutates until mutation_k genes of active nodes were changed
+    // 3: geometric semantic mutation; needs add, sub, mul and sigmoid in the function set
+    #[arg(long, default_value_t = 0)]
+    mutation_type: usize,
+
+    #[arg(long, default_value_t = 0.05)]
+    mutation_rate: f32,
+
+    #[arg(long, default_value_t = 1)]
+    mutation_k: usize,
+
+    // fitness of a single output node; every metric is minimised and has its own stopping target
+    // mae, mse, rmse, nrmse, r2 (as 1 - R²), hits (as the share of misses)
+    #[arg(long, default_value_t = DEFAULT_METRIC.to_string())]
+    fitness_metric: String,
+
+    // combines the fitness of multiple output nodes
+    // 0: mean
+    // 1: max
+    // 2: weighted sum, see output_weights
+    #[arg(long, default_value_t = 0)]
+    fitness_reduction: usize,
+
+    // one weight per output node, e.g. "0.7,0.3"
+    #[arg(long, value_delimiter = ',')]
+    output_weights: Vec<f32>,
+
+    // scores each output after fitting intercept and slope to the labels; the coefficients of
+    // the training data are kept for the test data and the saved program
+    #[arg(long)]
+    linear_scaling: bool,
+
+    // comma separated selection of the function registry:
+    // add, sub, mul, pdiv, sin, cos, ln, exp, sqrt, square, tanh, inv, abs, pow, sigmoid, fma, const
+    // const: node with an evolvable constant gene
+    #[arg(long, value_delimiter = ',', default_values_t = DEFAULT_FUNCTIONS.map(String::from))]
+    functions: Vec<String>,
+
+    // constants of const nodes are initialised uniformly in [-constant_range, constant_range]
+    #[arg(long, default_value_t = 1.0)]
+    constant_range: f32,
+
+    // standard deviation of the Gaussian mutation of constants
+    #[arg(long, default_value_t = 0.1)]
+    constant_sigma: f32,
+
+    // tunes the constants of the elitists with Nelder-Mead every n generations; 0: disabled
+    // only used by the runners with elitists (runner_type 1 and 2)
+    #[arg(long, default_value_t = 0)]
+    constant_optimisation_interval: usize,
+
+    // maximum number of evaluations per tuned elitist
+    #[arg(long, default_value_t = 100)]
+    constant_optimisation_evaluations: usize,
+
+    // tunes the constants of the best solution once the evolution stopped, with every runner;
+    // with max_evaluations, constant_optimisation_evaluations are kept for it
+    #[arg(long)]
+    constant_optimisation_at_end: bool,
+
+    // stops the run after this many evaluations on the training data, including constant
+    // tuning but not the fitness cache hits; 0: unlimited
+    #[arg(long, default_value_t = 0)]
+    max_evaluations: usize,
+
+    // seeds the whole run, including the sampling of the datasets
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
 
@@ -68,37 +170,59 @@ fn main() {
     // ################################################################################
     let args = Args::parse();
 
+    // separate stream, so sampling the dataset does not share random numbers with the runner
+    let mut rng = ChaCha8Rng::seed_from_u64(args.seed);
+    rng.set_stream(1);
+
     let ((data, label),
         (eval_data, eval_label)) = match args.dataset {
         0 => (keijzer::get_dataset(), keijzer::get_eval_dataset()),
-        1 => (koza_3::get_dataset(), koza_3::get_eval_dataset()),
-        2 => (nguyen_7::get_dataset(), nguyen_7::get_eval_dataset()),
+        1 => (koza_3::get_dataset(&mut rng), koza_3::get_eval_dataset(&mut rng)),
+        2 => (nguyen_7::get_dataset(&mut rng), nguyen_7::get_eval_dataset(&mut rng)),
         3 => (pagie_1::get_dataset(), pagie_1::get_eval_dataset()),
+        4 => (polynomial_system::get_dataset(), polynomial_system::get_eval_dataset()),
         _ => panic!("Wrong dataset"),
     };
 
     let mut params = CgpParameters::default();
 
     let nbr_inputs = data[0].len();
-    let nbr_outputs = 1;
+    let nbr_outputs = label[0].len();
 
     params.nbr_inputs = nbr_inputs;
     params.nbr_outputs = nbr_outputs;
     params.nbr_computational_nodes = args.nbr_nodes;
+    assert_eq!(args.nbr_nodes % args.nbr_rows, 0, "nbr_nodes must be divisible by nbr_rows");
+    params.nbr_rows = args.nbr_rows;
+    params.nbr_columns = args.nbr_nodes / args.nbr_rows;
+    params.levels_back = match args.levels_back {
+        0 => params.nbr_columns,
+        levels_back => levels_back,
+    };
     params.crossover_type = args.crossover_type;
     params.crossover_rate = args.crossover_rate;
+    params.crossover_include_outputs = args.crossover_include_outputs;
     params.tournament_size = args.tournament_size;
     params.elitism_number = args.elitism_number;
     params.multi_point_n = args.multi_point_n;
     params.population_size = args.population_size;
     params.cgp_type = args.cgp_type;
+    params.mutation_type = args.mutation_type;
+    params.mutation_rate = args.mutation_rate;
+    params.mutation_k = args.mutation_k;
+    params.constant_range = args.constant_range;
+    params.constant_sigma = args.constant_sigma;
+    params.constant_optimisation_interval = args.constant_optimisation_interval;
+    params.constant_optimisation_evaluations = args.constant_optimisation_evaluations;
+    params.max_evaluations = args.max_evaluations;
+    params.seed = args.seed;
 
     // ################################################################################
     // ############################ Logger ############################################
     // ################################################################################
 
     let runner_type = match args.runner_type {
-        0 => format!("one_plus_four"),
+        0 => "one_plus_four".to_string(),
         1 => format!("mu_{}_lambda_{}", args.elitism_number, args.population_size),
         2 => format!("tournament_pop_size_{}_t_size_{}_elitism_{}", args.population_size, args.tournament_size, args.elitism_number),
         _ => panic!("wrong runner type"),
@@ -109,22 +233,108 @@ fn main() {
         1 => "koza_3",
         2 => "nguyen_z",
         3 => "pagie_1",
+        4 => "polynomial_system",
         _ => panic!("Wrong dataset"),
     };
 
     let cgp_type_string = match args.cgp_type {
-        0 => format!("Baseline_standard"),
-        1 => format!("ereorder"),
+        0 => "Baseline_standard",
+        1 => "ereorder",
+        2 => "dag",
         _ => panic!("Wrong type"),
     };
 
     let crossover_type = match args.crossover_type {
-        0 => format!("point_crossover"),
+        0 => "point_crossover".to_string(),
         1 => format!("multi_{}_crossover", args.multi_point_n),
-        2 => format!("uniform_crossover"),
-        3 => format!("no_crossover"),
+        2 => "uniform_crossover".to_string(),
+        3 => "no_crossover".to_string(),
+        4 => "subgraph_crossover".to_string(),
+        5 => "discrete_crossover".to_string(),
+        6 => "block_crossover".to_string(),
+        7 => "output_swap_crossover".to_string(),
+        8 => "homologous_crossover".to_string(),
+        9 => "semantic_crossover".to_string(),
         _ => panic!("Wrong type"),
     };
+    let crossover_type = if args.crossover_include_outputs {
+        format!("{}_with_outputs", crossover_type)
+    } else {
+        crossover_type
+    };
+
+    let reduction = match args.fitness_reduction {
+        0 => FitnessReduction::Mean,
+        1 => FitnessReduction::Max,
+        2 => {
+            assert_eq!(args.output_weights.len(), nbr_outputs, "one weight per output is required");
+            FitnessReduction::weighted(args.output_weights.clone())
+        }
+        _ => panic!("Wrong fitness reduction"),
+    };
+
+    // multiple outputs are labeled by their fitness reduction
+    let output_string = match (nbr_outputs, &reduction) {
+        (1, _) => "single".to_string(),
+        (_, FitnessReduction::Mean) => "mean".to_string(),
+        (_, FitnessReduction::Max) => "max".to_string(),
+        (_, FitnessReduction::Weighted(weights)) => format!("weighted_{}", weights.iter().map(|w| w.to_string()).collect::<Vec<String>>().join("_")),
+    };
+    let output_string = if args.fitness_metric == DEFAULT_METRIC {
+        output_string
+    } else {
+        format!("{}_metric_{}", output_string, args.fitness_metric)
+    };
+    let output_string = if args.linear_scaling {
+        format!("{}_linear_scaling", output_string)
+    } else {
+        output_string
+    };
+
+    // only a non-default function set changes the folder name
+    let functions_string = if args.functions == DEFAULT_FUNCTIONS {
+        String::new()
+    } else {
+        format!("_functions_{}", args.functions.join("_"))
+    };
+
+    // only non-default constants change the folder name
+    let default_params = CgpParameters::default();
+    let constants_string = if !args.functions.iter().any(|function| function == "const")
+        || (args.constant_range == default_params.constant_range && args.constant_sigma == default_params.constant_sigma) {
+        String::new()
+    } else {
+        format!("_constants_range_{}_sigma_{}", args.constant_range, args.constant_sigma)
+    };
+    let constants_string = match args.constant_optimisation_interval {
+        0 => constants_string,
+        interval => format!("{}_tuning_{}_evaluations_{}", constants_string, interval, args.constant_optimisation_evaluations),
+    };
+    let constants_string = if args.constant_optimisation_at_end {
+        format!("{}_tuning_at_end_evaluations_{}", constants_string, args.constant_optimisation_evaluations)
+    } else {
+        constants_string
+    };
+    let constants_string = match args.max_evaluations {
+        0 => constants_string,
+        max_evaluations => format!("{}_max_evaluations_{}", constants_string, max_evaluations),
+    };
+
+    // only a non-default mutation changes the folder name
+    let mutation_string = match args.mutation_type {
+        0 => String::new(),
+        1 => format!("_mutation_prob_{}", args.mutation_rate),
+        2 => format!("_mutation_k_active_{}", args.mutation_k),
+        3 => "_mutation_semantic".to_string(),
+        _ => panic!("Wrong mutation type"),
+    };
+
+    // only a restricted grid changes the folder name
+    let grid_string = if params.nbr_rows == 1 && params.levels_back == params.nbr_columns {
+        String::new()
+    } else {
+        format!("_rows_{}_levels_back_{}", params.nbr_rows, params.levels_back)
+    };
 
     let save_path = Path::new("")
         .join("Experiments_Output_regression")
@@ -132,46 +342,82 @@ fn main() {
         .join(cgp_type_string)
         .join(crossover_type)
         .join(dataset_string)
-        .join(format!("number_nodes_{}_{}", args.nbr_nodes, "single"));
+        .join(format!("number_nodes_{}_{}{}{}{}{}", args.nbr_nodes, output_string, grid_string, functions_string, constants_string, mutation_string));
+
+    fs::create_dir_all(&save_path).unwrap();
+
+    // transpose so a whole row of the dataset can be used as an array for calculation
+    let data = transpose(data);
+    let label = transpose(label);
+    let eval_data = transpose(eval_data);
+    let eval_label = transpose(eval_label);
+    let domain = RegressionDomain::new(get_function_set(&args.functions),
+                                      get_metric(&args.fitness_metric),
+                                      reduction,
+                                      args.linear_scaling);
+    if args.crossover_type == 9 || args.mutation_type == 3 {
+        assert!(domain.semantic_functions().is_some(), "The semantic operators need add, sub, mul and sigmoid in the function set");
+    }
 
-    fs::create_dir_all(save_path.clone()).unwrap();
+    match args.cgp_type {
+        0 => run::<StandardChromosome<RegressionDomain>>(&args, params, domain, data, label, eval_data, eval_label, &save_path),
+        1 => run::<ReorderChromosome<RegressionDomain>>(&args, params, domain, data, label, eval_data, eval_label, &save_path),
+        2 => run::<DagChromosome<RegressionDomain>>(&args, params, domain, data, label, eval_data, eval_label, &save_path),
+        _ => panic!("Wrong type"),
+    }
+}
 
+#[allow(clippy::too_many_arguments)]
+fn run<G: Genotype<RegressionDomain>>(args: &Args,
+                                      params: CgpParameters,
+                                      domain: RegressionDomain,
+                                      data: Vec<Vec<f32>>,
+                                      label: Vec<Vec<f32>>,
+                                      eval_data: Vec<Vec<f32>>,
+                                      eval_label: Vec<Vec<f32>>,
+                                      save_path: &Path) {
     // ################################################################################
     // ############################ Training ##########################################
     // ################################################################################
-    let stdout = std::io::stdout();
-    let mut lock = stdout.lock();
-
-
     let save_file_iteration = format!("run_{}_iteration.txt", args.run_id);
     let mut output_file = File::create(save_path.join(save_file_iteration))
         .expect("cannot create file");
 
-    let mut runner = Runner::new(params.clone(),
-                                 data,
-                                 label,
-                                 eval_data,
-                                 eval_label);
+    // kept to report all metrics of the best solution
+    let datasets = [("Train", DatasetId::Training, data.clone(), label.clone()),
+                    ("Eval", DatasetId::Evaluation, eval_data.clone(), eval_label.clone())];
+    let metric = domain.metric;
+
+    let mut runner: Box<dyn EvolutionaryRunner<RegressionDomain, G>> = match args.runner_type {
+        0 => Box::new(OnePlusFourRunner::new(params, domain.clone(), data, label, eval_data, eval_label)),
+        1 => Box::new(MuLambdaRunner::new(params, domain.clone(), data, label, eval_data, eval_label)),
+        2 => Box::new(TournamentRunner::new(params, domain.clone(), data, label, eval_data, eval_label)),
+        _ => panic!("wrong runner type"),
+    };
     let mut runtime: usize = 0;
-    let mut fitness_eval = f32::MAX;
-    let mut fitness_train = f32::MAX;
 
-    while runtime < 500_000 {
+    // the evolution leaves the evaluations of the final tuning
+    let max_evaluations = if args.constant_optimisation_at_end {
+        args.max_evaluations.saturating_sub(args.constant_optimisation_evaluations)
+    } else {
+        args.max_evaluations
+    };
+    while runtime < 500_000 && (args.max_evaluations == 0 || runner.get_nbr_evaluations() < max_evaluations) {
         writeln!(output_file, "Iteration: {runtime}, Fitness: {:?}", runner.get_best_fitness()).expect("write not okay??");
-        // if runtime % params.eval_after_iterations == 0 {
-        //     writeln!(lock, "Iteration: {runtime}, Fitness: {:?}", runner.get_best_fitness()).expect("write not okay??");
-        // }
 
         runner.learn_step(runtime);  // lern step
 
         runtime += 1;
 
-        if float_eq!(runner.get_best_fitness(), 0., abs <= 0.01) {  // for single parent
+        if runner.get_best_fitness() <= metric.target {
             break;
         }
     }
-    fitness_eval = runner.get_test_fitness();
-    fitness_train = runner.get_best_fitness();
+    if args.constant_optimisation_at_end {
+        runner.optimise_best_constants();
+    }
+    let fitness_eval = runner.get_test_fitness();
+    let fitness_train = runner.get_best_fitness();
 
     // ################################################################################
     // ############################ Saving to text ####################################
@@ -179,6 +425,18 @@ fn main() {
     println!("{runtime}");
 
     writeln!(output_file, "End at iteration: {}", runtime).expect("cannot write");
+    writeln!(output_file, "Evaluations: {}", runner.get_nbr_evaluations()).expect("cannot write");
+    writeln!(output_file, "Cache hits: {}", runner.get_nbr_cache_hits()).expect("cannot write");
+    writeln!(output_file, "Cache misses: {}", runner.get_nbr_cache_misses()).expect("cannot write");
+    writeln!(output_file, "Semantic crossover failures: {} of {} ({})",
+             runner.get_nbr_semantic_crossover_failures(),
+             runner.get_nbr_crossovers(),
+             get_rate(runner.get_nbr_semantic_crossover_failures(), runner.get_nbr_crossovers())).expect("cannot write");
+    writeln!(output_file, "Semantic mutation failures: {} of {} ({})",
+             runner.get_nbr_semantic_mutation_failures(),
+             runner.get_nbr_mutations(),
+             get_rate(runner.get_nbr_semantic_mutation_failures(), runner.get_nbr_mutations())).expect("cannot write");
+    writeln!(output_file, "Reorder skips: {}", runner.get_nbr_reorder_skips()).expect("cannot write");
     writeln!(output_file, "Fitness Eval: {}", fitness_eval).expect("cannot write");
     writeln!(output_file, "Fitness Train: {}", fitness_train).expect("cannot write");
 
@@ -186,8 +444,35 @@ fn main() {
     let mut output = File::create(save_path.join(save_file_active_node))
         .expect("cannot create file");
 
-    let mut parent = runner.get_parent();
+    let mut parent = runner.get_best_solution();
     parent.get_active_nodes_id();
 
-    write!(output, "{:?}", parent.active_nodes.unwrap()).expect("cannot write");
+    write!(output, "{:?}", parent.active_nodes()).expect("cannot write");
+
+    // every metric of the best solution, with the output transform fitted on the training data
+    let mut column_store = ColumnStore::default();
+    for (set, dataset, data, label) in &datasets {
+        let outputs = parent.predict(data, *dataset, &mut column_store);
+        let outputs: Vec<&[f32]> = outputs.iter().map(|output| output.as_slice()).collect();
+
+        for metric in METRIC_REGISTRY {
+            let fitness = domain.transformed_metric_fitness(&metric, &outputs, label, parent.output_transform());
+            writeln!(output_file, "{} {}: {}", set, metric.name, metric.report(fitness)).expect("cannot write");
+        }
+    }
+
+    let save_file_program = format!("run_{}_program.txt", args.run_id);
+    let mut output = File::create(save_path.join(save_file_program))
+        .expect("cannot create file");
+
+    write!(output, "{}", parent.active_program()).expect("cannot write");
+}
+
+/// `part` as a percentage of `total`, "-" if `total` is 0.
+fn get_rate(part: usize, total: usize) -> String {
+    if total == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}%", 100. * part as f32 / total as f32)
+    }
 }
@@ -0,0 +1,132 @@
+use cgp_core::value_domain::{SemanticFunctions, ValueDomain};
+use crate::utils::symbolic_regression_functions as function_set;
+use crate::utils::symbolic_regression_functions::RegressionFunction;
+use crate::utils::fitness_metrics;
+use crate::utils::fitness_metrics::{FitnessMetric, FitnessReduction};
+
+/// Symbolic regression over `f32`. The function set is a selection of the function registry,
+/// function ids are indices into `functions`.
+/// Each output node is scored on its own by `metric`; `reduction` combines them into one
+/// fitness value.
+/// With `linear_scaling`, each output is scaled by the least squares fit of intercept and
+/// slope to its labels before scoring (Keijzer, 2003).
+#[derive(Clone)]
+pub struct RegressionDomain {
+    pub functions: Vec<RegressionFunction>,
+    pub metric: FitnessMetric,
+    pub reduction: FitnessReduction,
+    pub linear_scaling: bool,
+}
+
+impl RegressionDomain {
+    pub fn new(functions: Vec<RegressionFunction>,
+               metric: FitnessMetric,
+               reduction: FitnessReduction,
+               linear_scaling: bool) -> Self {
+        Self {
+            functions,
+            metric,
+            reduction,
+            linear_scaling,
+        }
+    }
+
+    /// Fitness of `outputs` under `metric`, which need not be the metric that is optimised.
+    pub fn metric_fitness(&self, metric: &FitnessMetric, outputs: &[&[f32]], labels: &[Vec<f32>]) -> f32 {
+        assert_eq!(outputs.len(), labels.len());
+
+        let fitness_per_output: Vec<f32> = outputs
+            .iter()
+            .zip(labels.iter())
+            .map(|(prediction, label)| (metric.fitness)(prediction, label))
+            .collect();
+
+        fitness_metrics::reduce_fitness(&fitness_per_output, &self.reduction)
+    }
+
+    /// Like `metric_fitness`, after applying `output_transform` to the outputs.
+    pub fn transformed_metric_fitness(&self,
+                                      metric: &FitnessMetric,
+                                      outputs: &[&[f32]],
+                                      labels: &[Vec<f32>],
+                                      output_transform: &[(f32, f32)]) -> f32 {
+        if output_transform.is_empty() {
+            return self.metric_fitness(metric, outputs, labels);
+        }
+
+        let scaled_outputs: Vec<Vec<f32>> = outputs.iter()
+            .zip(output_transform.iter())
+            .map(|(prediction, (intercept, slope))| prediction.iter().map(|p| intercept + slope * p).collect())
+            .collect();
+        let scaled_outputs: Vec<&[f32]> = scaled_outputs.iter().map(|output| output.as_slice()).collect();
+
+        self.metric_fitness(metric, &scaled_outputs, labels)
+    }
+}
+
+impl ValueDomain for RegressionDomain {
+    type Value = f32;
+    /// Intercept and slope per output node; empty without linear scaling.
+    type OutputTransform = Vec<(f32, f32)>;
+
+    fn nbr_functions(&self) -> usize {
+        self.functions.len()
+    }
+
+    fn arity(&self, function_id: usize) -> usize {
+        self.functions[function_id].arity
+    }
+
+    fn execute(&self, function_id: usize, inputs: &[&[f32]], output: &mut [f32]) {
+        (self.functions[function_id].execute)(inputs, output)
+    }
+
+    fn display_function(&self, function_id: usize, inputs: &[String]) -> String {
+        function_set::display_function(&self.functions[function_id], inputs)
+    }
+
+    fn constant_function(&self) -> Option<usize> {
+        self.functions.iter().position(|function| function.name == "const")
+    }
+
+    fn constant_value(&self, constant: f32) -> Option<f32> {
+        self.constant_function().map(|_| constant)
+    }
+
+    fn semantic_functions(&self) -> Option<SemanticFunctions> {
+        let function_id = |name: &str| self.functions.iter().position(|function| function.name == name);
+
+        Some(SemanticFunctions {
+            add: function_id("add")?,
+            sub: function_id("sub")?,
+            mul: function_id("mul")?,
+            sigmoid: function_id("sigmoid")?,
+        })
+    }
+
+    fn fitness(&self, outputs: &[&[f32]], labels: &[Vec<f32>]) -> f32 {
+        self.metric_fitness(&self.metric, outputs, labels)
+    }
+
+    fn fit_output_transform(&self, outputs: &[&[f32]], labels: &[Vec<f32>]) -> Vec<(f32, f32)> {
+        if !self.linear_scaling {
+            return vec![];
+        }
+
+        outputs.iter()
+            .zip(labels.iter())
+            .map(|(prediction, label)| fitness_metrics::linear_scaling_coefficients(prediction, label))
+            .collect()
+    }
+
+    fn transformed_fitness(&self, outputs: &[&[f32]], labels: &[Vec<f32>], output_transform: &Vec<(f32, f32)>) -> f32 {
+        self.transformed_metric_fitness(&self.metric, outputs, labels, output_transform)
+    }
+
+    fn display_output(&self, output_id: usize, input: String, output_transform: &Vec<(f32, f32)>) -> String {
+        match output_transform.get(output_id) {
+            Some((intercept, slope)) => format!("{} + {} * {}", intercept, slope, input),
+            None => input,
+        }
+    }
+}
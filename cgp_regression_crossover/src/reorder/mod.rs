@@ -1,3 +0,0 @@
-pub mod node_reorder;
-pub mod linspace;
-pub mod chromosome_reorder_equidistant;
@@ -1,2 +0,0 @@
-pub mod node;
-pub mod chromosome;
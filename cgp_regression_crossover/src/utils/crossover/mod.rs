@@ -1,4 +0,0 @@
-#[cfg(feature = "tournament")]
-pub mod crossover_algos;
-#[cfg(feature = "mulambda_crossover")]
-pub mod crossover_algos;
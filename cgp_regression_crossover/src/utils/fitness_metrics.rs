@@ -1,12 +1,236 @@
-
-pub fn fitness_regression(prediction: &Vec<f32>, label: &Vec<f32>) -> f32 {
-    assert_eq!(prediction.len(), label.len());
-    let mut fitness: f32 = 0.;
-    prediction.iter().zip(label.iter()).for_each(|(x, y)| fitness +=  (x - y).abs() );
-
-    fitness = fitness / (prediction.len() as f32);
-
-    return fitness;
-
-
-}
\ No newline at end of file
+/// Mean absolute error.
+pub fn fitness_regression(prediction: &[f32], label: &[f32]) -> f32 {
+    assert_eq!(prediction.len(), label.len());
+    let mut fitness: f32 = 0.;
+    prediction.iter().zip(label.iter()).for_each(|(x, y)| fitness += (x - y).abs());
+
+    fitness / (prediction.len() as f32)
+}
+
+pub fn mean_squared_error(prediction: &[f32], label: &[f32]) -> f32 {
+    assert_eq!(prediction.len(), label.len());
+    let mut fitness: f32 = 0.;
+    prediction.iter().zip(label.iter()).for_each(|(x, y)| fitness += (x - y) * (x - y));
+
+    fitness / (prediction.len() as f32)
+}
+
+pub fn root_mean_squared_error(prediction: &[f32], label: &[f32]) -> f32 {
+    mean_squared_error(prediction, label).sqrt()
+}
+
+/// RMSE divided by the standard deviation of the labels, see `relative_squared_error` for
+/// constant labels.
+pub fn normalised_root_mean_squared_error(prediction: &[f32], label: &[f32]) -> f32 {
+    relative_squared_error(prediction, label).sqrt()
+}
+
+/// `1 - R²`, i.e. the residual sum of squares relative to the total sum of squares, see
+/// `relative_squared_error` for constant labels.
+pub fn unexplained_variance(prediction: &[f32], label: &[f32]) -> f32 {
+    relative_squared_error(prediction, label)
+}
+
+/// Share of samples whose prediction misses the label by more than `HITS_TOLERANCE`.
+pub fn misses(prediction: &[f32], label: &[f32]) -> f32 {
+    assert_eq!(prediction.len(), label.len());
+    let nbr_hits = prediction.iter()
+        .zip(label.iter())
+        .filter(|(x, y)| (*x - *y).abs() <= HITS_TOLERANCE)
+        .count();
+
+    (prediction.len() - nbr_hits) as f32 / (prediction.len() as f32)
+}
+
+/// MSE divided by the variance of the labels. Constant labels have no variance to divide by;
+/// then an exact prediction scores 0 and any other 1, the score of predicting the mean.
+/// NaN stays NaN.
+fn relative_squared_error(prediction: &[f32], label: &[f32]) -> f32 {
+    let mse = mean_squared_error(prediction, label);
+    let variance = label_variance(label);
+
+    if variance > f32::EPSILON || mse.is_nan() {
+        mse / variance
+    } else if mse <= f32::EPSILON {
+        0.
+    } else {
+        1.
+    }
+}
+
+fn label_variance(label: &[f32]) -> f32 {
+    let nbr_samples = label.len() as f32;
+    let mean_label = label.iter().sum::<f32>() / nbr_samples;
+
+    label.iter().map(|y| (y - mean_label) * (y - mean_label)).sum::<f32>() / nbr_samples
+}
+
+/// A prediction within this distance of its label is a hit.
+pub const HITS_TOLERANCE: f32 = 0.01;
+
+/// An entry of the metric registry. Every metric is minimised; metrics that are usually
+/// maximised are stored as their complement, i.e. R² as `1 - R²` and hits as the share of misses.
+#[derive(Clone, Copy)]
+pub struct FitnessMetric {
+    pub name: &'static str,
+    /// Fitness of one output node.
+    pub fitness: fn(&[f32], &[f32]) -> f32,
+    /// A run stops once the best fitness is at or below this value.
+    pub target: f32,
+    /// Reported as `1 - fitness`.
+    pub complement: bool,
+}
+
+impl FitnessMetric {
+    /// Value of the metric as it is usually reported.
+    pub fn report(&self, fitness: f32) -> f32 {
+        if self.complement { 1. - fitness } else { fitness }
+    }
+}
+
+pub const METRIC_REGISTRY: [FitnessMetric; 6] = [
+    FitnessMetric { name: "mae", fitness: fitness_regression, target: 0.01, complement: false },
+    FitnessMetric { name: "mse", fitness: mean_squared_error, target: 0.0001, complement: false },
+    FitnessMetric { name: "rmse", fitness: root_mean_squared_error, target: 0.01, complement: false },
+    FitnessMetric { name: "nrmse", fitness: normalised_root_mean_squared_error, target: 0.01, complement: false },
+    FitnessMetric { name: "r2", fitness: unexplained_variance, target: 0.0001, complement: true },
+    // all samples have to be hit
+    FitnessMetric { name: "hits", fitness: misses, target: 0., complement: true },
+];
+
+/// The metric used before the registry existed.
+pub const DEFAULT_METRIC: &str = "mae";
+
+pub fn get_metric(name: &str) -> FitnessMetric {
+    *METRIC_REGISTRY.iter()
+        .find(|metric| metric.name == name.trim())
+        .unwrap_or_else(|| panic!("Unknown fitness metric: {}", name))
+}
+
+/// Intercept and slope of the least squares fit of `prediction` to `label`.
+/// A constant prediction gets a slope of 0, i.e. it is replaced by the mean of the labels.
+pub fn linear_scaling_coefficients(prediction: &[f32], label: &[f32]) -> (f32, f32) {
+    assert_eq!(prediction.len(), label.len());
+    let nbr_samples = prediction.len() as f32;
+
+    let mean_prediction = prediction.iter().sum::<f32>() / nbr_samples;
+    let mean_label = label.iter().sum::<f32>() / nbr_samples;
+
+    let mut covariance: f32 = 0.;
+    let mut variance: f32 = 0.;
+    prediction.iter().zip(label.iter()).for_each(|(p, y)| {
+        covariance += (p - mean_prediction) * (y - mean_label);
+        variance += (p - mean_prediction) * (p - mean_prediction);
+    });
+
+    let slope = if variance > f32::EPSILON { covariance / variance } else { 0. };
+    (mean_label - slope * mean_prediction, slope)
+}
+
+/// How the fitness values of multiple output nodes are combined into one.
+#[derive(Clone, Debug)]
+pub enum FitnessReduction {
+    Mean,
+    Max,
+    /// One weight per output node.
+    Weighted(Vec<f32>),
+}
+
+impl FitnessReduction {
+    pub fn weighted(weights: Vec<f32>) -> Self {
+        assert!(weights.iter().all(|w| w.is_finite() && *w >= 0.),
+                "output weights must be finite and not negative: {:?}", weights);
+        FitnessReduction::Weighted(weights)
+    }
+}
+
+pub fn reduce_fitness(fitness_per_output: &[f32], reduction: &FitnessReduction) -> f32 {
+    // `f32::max` drops NaN, so a single broken output has to be caught before the reduction
+    if fitness_per_output.iter().any(|x| x.is_nan()) {
+        return f32::NAN;
+    }
+
+    match reduction {
+        FitnessReduction::Mean => fitness_per_output.iter().sum::<f32>() / (fitness_per_output.len() as f32),
+        FitnessReduction::Max => fitness_per_output.iter().fold(f32::MIN, |acc, x| acc.max(*x)),
+        FitnessReduction::Weighted(weights) => {
+            assert_eq!(weights.len(), fitness_per_output.len(), "one weight per output is required");
+            fitness_per_output.iter().zip(weights.iter()).map(|(x, w)| x * w).sum()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    const LABEL: [f32; 4] = [1., 2., 3., 4.];
+    /// Errors of 0.5, 0, -1 and 0; the labels have a mean of 2.5 and a variance of 1.25.
+    const PREDICTION: [f32; 4] = [1.5, 2., 2., 4.];
+
+    fn shifted_label(shift: f32) -> Vec<f32> {
+        LABEL.iter().map(|y| y + shift).collect()
+    }
+
+    #[test]
+    fn metrics_match_hand_computed_values() {
+        assert_float_eq!(fitness_regression(&PREDICTION, &LABEL), 0.375, abs <= 1e-6);
+        assert_float_eq!(mean_squared_error(&PREDICTION, &LABEL), 0.3125, abs <= 1e-6);
+        assert_float_eq!(root_mean_squared_error(&PREDICTION, &LABEL), 0.559_017, abs <= 1e-6);
+        // 0.559017 / sqrt(1.25)
+        assert_float_eq!(normalised_root_mean_squared_error(&PREDICTION, &LABEL), 0.5, abs <= 1e-6);
+        // 0.3125 / 1.25
+        assert_float_eq!(unexplained_variance(&PREDICTION, &LABEL), 0.25, abs <= 1e-6);
+        assert_float_eq!(misses(&PREDICTION, &LABEL), 0.5, abs <= 1e-6);
+
+        assert_float_eq!(get_metric("r2").report(0.25), 0.75, abs <= 1e-6);
+        assert_float_eq!(get_metric("hits").report(0.5), 0.5, abs <= 1e-6);
+        assert_float_eq!(get_metric("mae").report(0.375), 0.375, abs <= 1e-6);
+    }
+
+    #[test]
+    fn targets_separate_close_from_distant_predictions() {
+        for metric in METRIC_REGISTRY {
+            // within the hit tolerance of every label
+            let close = (metric.fitness)(&shifted_label(0.5 * HITS_TOLERANCE), &LABEL);
+            assert!(close <= metric.target, "{}: {} above target {}", metric.name, close, metric.target);
+
+            let distant = (metric.fitness)(&shifted_label(0.05), &LABEL);
+            assert!(distant > metric.target, "{}: {} at or below target {}", metric.name, distant, metric.target);
+
+            assert_eq!((metric.fitness)(&LABEL, &LABEL), 0., "{}", metric.name);
+        }
+    }
+
+    #[test]
+    fn constant_labels_score_exact_predictions_only() {
+        let label = [0.1; 3];
+
+        for metric in [get_metric("nrmse"), get_metric("r2")] {
+            assert_eq!((metric.fitness)(&label, &label), 0., "{}", metric.name);
+            assert_eq!((metric.fitness)(&[0.1, 0.2, 0.1], &label), 1., "{}", metric.name);
+            assert!((metric.fitness)(&[0.1, f32::NAN, 0.1], &label).is_nan(), "{}", metric.name);
+        }
+    }
+
+    #[test]
+    fn nan_predictions_propagate() {
+        let prediction = [1., f32::NAN, 3., 4.];
+
+        for metric in METRIC_REGISTRY {
+            let fitness = (metric.fitness)(&prediction, &LABEL);
+            if metric.name == "hits" {
+                // a NaN prediction is a miss
+                assert_float_eq!(fitness, 0.25, abs <= 1e-6);
+            } else {
+                assert!(fitness.is_nan(), "{}: {}", metric.name, fitness);
+            }
+        }
+
+        // the runners replace non-finite fitness values by f32::MAX, so NaN must survive the reduction
+        for reduction in [FitnessReduction::Mean, FitnessReduction::Max, FitnessReduction::weighted(vec![1., 0.])] {
+            assert!(reduce_fitness(&[0.5, f32::NAN], &reduction).is_nan(), "{:?}", reduction);
+        }
+    }
+}
@@ -1,11 +1,3 @@
-pub mod fitness_metrics;
-pub mod runner;
-pub mod node_type;
-pub mod utility_funcs;
-pub mod symbolic_regression_functions;
-#[cfg(feature = "tournament")]
-pub mod runner_multiple_parents_with_elitist_tournament;
-#[cfg(feature = "mulambda_crossover")]
-pub mod runner_multiple_parents_with_elitist_mulambda;
-
-pub mod crossover;
\ No newline at end of file
+pub mod fitness_metrics;
+pub mod utility_funcs;
+pub mod symbolic_regression_functions;
@@ -1,49 +1,167 @@
-use float_eq::float_eq;
-
-pub fn add(con1: &Vec<f32>, con2: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().zip(con2.iter()).map(|(&a, &b)| a + b).collect();
-}
-
-pub fn subtract(con1: &Vec<f32>, con2: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().zip(con2.iter()).map(|(&a, &b)| a - b).collect();
-}
-
-pub fn mul(con1: &Vec<f32>, con2: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().zip(con2.iter()).map(|(&a, &b)| a * b).collect();
-}
-
-pub fn div(con1: &Vec<f32>, con2: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().zip(con2.iter()).map(|(&a, &b)| {
-        if float_eq!(b, 0.0, abs <= 0.000_01) {
-            return 1.;
-        } else {
-            return a / b;
-        }
-    }).collect();
-}
-
-pub fn sin(con1: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().map(|&x| x.sin()).collect();
-}
-
-pub fn cos(con1: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().map(|&x| x.cos()).collect();
-}
-
-pub fn ln(con1: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().map(|&x| {
-        if float_eq!(x, 0.0, abs <= 0.000_01) {
-            return 1.;
-        } else {
-            return x.abs().ln();
-        }
-    }
-    ).collect();
-}
-
-pub fn exp(con1: &Vec<f32>) -> Vec<f32> {
-    return con1.iter().map(|&x| x.exp()).collect();
-}
-
-
-// +, - *, /, sin, cos, ln(|n|), e^n
\ No newline at end of file
+use float_eq::float_eq;
+
+// Kernels write into a preallocated output column of the same length as their inputs.
+
+pub fn add(con1: &[f32], con2: &[f32], output: &mut [f32]) {
+    binary(con1, con2, output, |a, b| a + b)
+}
+
+pub fn subtract(con1: &[f32], con2: &[f32], output: &mut [f32]) {
+    binary(con1, con2, output, |a, b| a - b)
+}
+
+pub fn mul(con1: &[f32], con2: &[f32], output: &mut [f32]) {
+    binary(con1, con2, output, |a, b| a * b)
+}
+
+pub fn div(con1: &[f32], con2: &[f32], output: &mut [f32]) {
+    binary(con1, con2, output, |a, b| {
+        if float_eq!(b, 0.0, abs <= 0.000_01) {
+            1.
+        } else {
+            a / b
+        }
+    })
+}
+
+pub fn sin(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x.sin())
+}
+
+pub fn cos(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x.cos())
+}
+
+pub fn ln(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| {
+        if float_eq!(x, 0.0, abs <= 0.000_01) {
+            1.
+        } else {
+            x.abs().ln()
+        }
+    })
+}
+
+pub fn exp(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x.exp())
+}
+
+pub fn sqrt(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x.abs().sqrt())
+}
+
+pub fn square(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x * x)
+}
+
+pub fn tanh(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x.tanh())
+}
+
+pub fn inv(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| {
+        if float_eq!(x, 0.0, abs <= 0.000_01) {
+            1.
+        } else {
+            1. / x
+        }
+    })
+}
+
+pub fn abs(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| x.abs())
+}
+
+pub fn pow(con1: &[f32], con2: &[f32], output: &mut [f32]) {
+    binary(con1, con2, output, |a, b| {
+        let result = a.abs().powf(b);
+        if result.is_finite() {
+            result
+        } else {
+            1.
+        }
+    })
+}
+
+pub fn sigmoid(con1: &[f32], output: &mut [f32]) {
+    unary(con1, output, |x| 1. / (1. + (-x).exp()))
+}
+
+pub fn fma(con1: &[f32], con2: &[f32], con3: &[f32], output: &mut [f32]) {
+    for (((o, &a), &b), &c) in output.iter_mut().zip(con1.iter()).zip(con2.iter()).zip(con3.iter()) {
+        *o = a.mul_add(b, c);
+    }
+}
+
+/// Element-wise loop over slices, simple enough for the compiler to vectorise.
+#[inline(always)]
+fn unary(con1: &[f32], output: &mut [f32], f: impl Fn(f32) -> f32) {
+    for (o, &x) in output.iter_mut().zip(con1.iter()) {
+        *o = f(x);
+    }
+}
+
+#[inline(always)]
+fn binary(con1: &[f32], con2: &[f32], output: &mut [f32], f: impl Fn(f32, f32) -> f32) {
+    for ((o, &a), &b) in output.iter_mut().zip(con1.iter()).zip(con2.iter()) {
+        *o = f(a, b);
+    }
+}
+
+/// An entry of the function registry.
+#[derive(Clone, Copy)]
+pub struct RegressionFunction {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Protected implementation; `inputs` holds `arity` columns, the result is written into
+    /// the last argument.
+    pub execute: fn(&[&[f32]], &mut [f32]),
+    /// Display form; `{0}`, `{1}`, ... are replaced by the inputs.
+    pub display: &'static str,
+}
+
+pub const FUNCTION_REGISTRY: [RegressionFunction; 17] = [
+    RegressionFunction { name: "add", arity: 2, execute: |x, out| add(x[0], x[1], out), display: "({0} + {1})" },
+    RegressionFunction { name: "sub", arity: 2, execute: |x, out| subtract(x[0], x[1], out), display: "({0} - {1})" },
+    RegressionFunction { name: "mul", arity: 2, execute: |x, out| mul(x[0], x[1], out), display: "({0} * {1})" },
+    RegressionFunction { name: "pdiv", arity: 2, execute: |x, out| div(x[0], x[1], out), display: "pdiv({0}, {1})" },
+    RegressionFunction { name: "sin", arity: 1, execute: |x, out| sin(x[0], out), display: "sin({0})" },
+    RegressionFunction { name: "cos", arity: 1, execute: |x, out| cos(x[0], out), display: "cos({0})" },
+    RegressionFunction { name: "ln", arity: 1, execute: |x, out| ln(x[0], out), display: "ln(|{0}|)" },
+    RegressionFunction { name: "exp", arity: 1, execute: |x, out| exp(x[0], out), display: "exp({0})" },
+    RegressionFunction { name: "sqrt", arity: 1, execute: |x, out| sqrt(x[0], out), display: "sqrt(|{0}|)" },
+    RegressionFunction { name: "square", arity: 1, execute: |x, out| square(x[0], out), display: "({0})^2" },
+    RegressionFunction { name: "tanh", arity: 1, execute: |x, out| tanh(x[0], out), display: "tanh({0})" },
+    RegressionFunction { name: "inv", arity: 1, execute: |x, out| inv(x[0], out), display: "pinv({0})" },
+    RegressionFunction { name: "abs", arity: 1, execute: |x, out| abs(x[0], out), display: "|{0}|" },
+    RegressionFunction { name: "pow", arity: 2, execute: |x, out| pow(x[0], x[1], out), display: "(|{0}| ^ {1})" },
+    RegressionFunction { name: "sigmoid", arity: 1, execute: |x, out| sigmoid(x[0], out), display: "sigmoid({0})" },
+    RegressionFunction { name: "fma", arity: 3, execute: |x, out| fma(x[0], x[1], x[2], out), display: "({0} * {1} + {2})" },
+    // outputs the constant gene of its node, see RegressionDomain::constant_value
+    RegressionFunction { name: "const", arity: 0, execute: |_, _| unreachable!("constants are filled in by the evaluator"), display: "const" },
+];
+
+/// The function set used before the registry existed; keeps the function ids of old runs.
+pub const DEFAULT_FUNCTIONS: [&str; 8] = ["add", "sub", "mul", "pdiv", "sin", "cos", "ln", "exp"];
+
+/// Looks up the registry entries for `names`, in the given order.
+pub fn get_function_set(names: &[String]) -> Vec<RegressionFunction> {
+    assert!(!names.is_empty(), "The function set must not be empty");
+
+    names.iter()
+        .map(|name| {
+            *FUNCTION_REGISTRY.iter()
+                .find(|function| function.name == name.trim())
+                .unwrap_or_else(|| panic!("Unknown function: {}", name))
+        })
+        .collect()
+}
+
+/// Fills the display form of `function` with `inputs`.
+pub fn display_function(function: &RegressionFunction, inputs: &[String]) -> String {
+    inputs.iter()
+        .enumerate()
+        .fold(function.display.to_string(), |display, (i, input)| {
+            display.replace(&format!("{{{}}}", i), input)
+        })
+}